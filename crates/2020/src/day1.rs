@@ -1,41 +1,148 @@
-use eyre::eyre;
-use itertools::Itertools;
+use std::convert::TryFrom;
 
-pub fn solve() -> eyre::Result<()> {
-    println!("day1.1: {}", solve1()?);
-    println!("day1.2: {}", solve2()?);
+use aoc_common::{Answer, Shape, Solution};
+use eyre::{eyre, Result};
 
-    Ok(())
+pub fn solve() -> Result<(i64, i64)> {
+    let input = crate::input(1)?;
+    Ok((solve1(&input)?, solve2(&input)?))
 }
 
-const INPUT: &str = include_str!("../input1.csv");
+pub struct Day1;
 
-fn solve1() -> eyre::Result<i32> {
-    let lines = INPUT
-        .lines()
-        .map(|it| it.parse::<i32>())
-        .collect::<Result<Vec<_>, _>>()?;
-    solve_for_combinations(&lines, 2)
+impl Solution for Day1 {
+    fn part1(&self, input: &str) -> Result<Answer> {
+        Ok(solve1(input)?.into())
+    }
+    fn part2(&self, input: &str) -> Result<Answer> {
+        Ok(solve2(input)?.into())
+    }
+    fn input_shape(&self) -> Option<Shape> {
+        Some(Shape::Lines(r"\d+"))
+    }
+}
+
+const TARGET: i64 = 2020;
+
+fn solve1(input: &str) -> Result<i64> {
+    product_of_entries(&parse(input)?, 2)
 }
 
-fn solve2() -> eyre::Result<i32> {
-    let lines = INPUT
+fn solve2(input: &str) -> Result<i64> {
+    product_of_entries(&parse(input)?, 3)
+}
+
+fn parse(input: &str) -> Result<Vec<i64>> {
+    Ok(input
         .lines()
-        .map(|it| it.parse::<i32>())
-        .collect::<Result<Vec<_>, _>>()?;
-    solve_for_combinations(&lines, 3)
+        .map(|it| it.parse::<i64>())
+        .collect::<Result<Vec<_>, _>>()?)
 }
 
-fn solve_for_combinations(lines: &[i32], combinations: usize) -> eyre::Result<i32> {
-    let result: i32 = lines
-        .iter()
-        .combinations(combinations)
-        .find(|it| it.iter().copied().sum::<i32>() == 2020)
-        .ok_or_else(|| eyre!("no pair found"))?
+/// The product of the `k` entries that sum to 2020.
+fn product_of_entries(entries: &[i64], k: usize) -> Result<i64> {
+    let sums = k_sum(entries, k, TARGET);
+    let entries = sums
+        .first()
+        .ok_or_else(|| eyre!("no {} entries sum to {}", k, TARGET))?;
+    entries
         .iter()
-        .copied()
-        .product();
-    Ok(result)
+        .try_fold(1i64, |product, &entry| product.checked_mul(entry))
+        .ok_or_else(|| eyre!("the product of {:?} overflows", entries))
+}
+
+/// Every combination of `k` of the `values` that sums to `target`, each sorted ascending and
+/// all in lexicographic order. A value can be used as often as it occurs, and combinations of
+/// equal values are only returned once. Empty if there is no solution.
+///
+/// Sorts the values and fixes all but two of them before finding the last two with two
+/// pointers, which takes O(n^(k-1)) time instead of the O(n^k) of trying every combination.
+pub fn k_sum(values: &[i64], k: usize, target: i64) -> Vec<Vec<i64>> {
+    let mut sorted = values.to_vec();
+    sorted.sort_unstable();
+    let mut sums = Vec::new();
+    let mut chosen = Vec::with_capacity(k);
+    k_sum_sorted(&sorted, k, target.into(), &mut chosen, &mut sums);
+    sums
+}
+
+/// Sums are calculated in `i128`, where no sum of `i64`s overflows.
+fn k_sum_sorted(
+    sorted: &[i64],
+    k: usize,
+    target: i128,
+    chosen: &mut Vec<i64>,
+    sums: &mut Vec<Vec<i64>>,
+) {
+    if k == 0 {
+        if target == 0 {
+            sums.push(chosen.clone());
+        }
+        return;
+    }
+    if sorted.len() < k {
+        return;
+    }
+    // Even the k smallest or largest values can't reach the target.
+    let (smallest, largest) = (sorted[0] as i128, sorted[sorted.len() - 1] as i128);
+    if smallest * (k as i128) > target || largest * (k as i128) < target {
+        return;
+    }
+
+    if k == 1 {
+        if let Ok(target) = i64::try_from(target) {
+            if sorted.binary_search(&target).is_ok() {
+                chosen.push(target);
+                sums.push(chosen.clone());
+                chosen.pop();
+            }
+        }
+        return;
+    }
+
+    if k == 2 {
+        let (mut low, mut high) = (0, sorted.len() - 1);
+        while low < high {
+            let sum = sorted[low] as i128 + sorted[high] as i128;
+            if sum < target {
+                low += 1;
+            } else if sum > target {
+                high -= 1;
+            } else {
+                let (a, b) = (sorted[low], sorted[high]);
+                chosen.extend([a, b]);
+                sums.push(chosen.clone());
+                chosen.truncate(chosen.len() - 2);
+                while low < high && sorted[low] == a {
+                    low += 1;
+                }
+                while low < high && sorted[high] == b {
+                    high -= 1;
+                }
+            }
+        }
+        return;
+    }
+
+    for i in 0..=sorted.len() - k {
+        let value = sorted[i];
+        if i > 0 && sorted[i - 1] == value {
+            continue;
+        }
+        // The rest of the combination is at least as large as this value.
+        if value as i128 * k as i128 > target {
+            break;
+        }
+        chosen.push(value);
+        k_sum_sorted(
+            &sorted[i + 1..],
+            k - 1,
+            target - value as i128,
+            chosen,
+            sums,
+        );
+        chosen.pop();
+    }
 }
 
 #[cfg(test)]
@@ -44,7 +151,81 @@ mod tests {
 
     #[test]
     fn day1() {
-        assert_eq!(solve1().unwrap(), 988771);
-        assert_eq!(solve2().unwrap(), 171933104);
+        let input = crate::input(1).unwrap();
+        assert_eq!(solve1(&input).unwrap(), 988771);
+        assert_eq!(solve2(&input).unwrap(), 171933104);
+    }
+
+    #[test]
+    fn k_sums() {
+        let report = [1721, 979, 366, 299, 675, 1456];
+        assert_eq!(k_sum(&report, 2, 2020), [[299, 1721]]);
+        assert_eq!(k_sum(&report, 3, 2020), [[366, 675, 979]]);
+        assert_eq!(k_sum(&report, 1, 979), [[979]]);
+        assert!(k_sum(&report, 2, 3).is_empty());
+        assert!(k_sum(&report, 7, 5496).is_empty());
+        assert_eq!(k_sum(&report, 0, 0), [Vec::<i64>::new()]);
+
+        let values = [-1, 0, 1, 2, -1, -4];
+        assert_eq!(k_sum(&values, 3, 0), [[-1, -1, 2], [-1, 0, 1]]);
+        assert_eq!(k_sum(&[2, 2, 2, 2], 2, 4), [[2, 2]]);
+        assert!(k_sum(&[2], 2, 4).is_empty());
+        assert_eq!(
+            k_sum(&[i64::MAX, i64::MAX, i64::MIN], 3, i64::MAX - 1),
+            [[i64::MIN, i64::MAX, i64::MAX]]
+        );
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        use itertools::Itertools;
+
+        let values: Vec<i64> = (0..40).map(|i| (i * 37 % 23) - 11).collect();
+        for k in 1..=4 {
+            for target in -20..=20 {
+                let expected: Vec<Vec<i64>> = values
+                    .iter()
+                    .copied()
+                    .combinations(k)
+                    .filter(|it| it.iter().sum::<i64>() == target)
+                    .map(|mut it| {
+                        it.sort_unstable();
+                        it
+                    })
+                    .sorted()
+                    .dedup()
+                    .collect();
+                assert_eq!(
+                    k_sum(&values, k, target),
+                    expected,
+                    "k={} target={}",
+                    k,
+                    target
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn large_lists() {
+        // The even numbers below 100000.
+        let values: Vec<i64> = (0..50_000).map(|i| i * 2).collect();
+        assert_eq!(k_sum(&values, 2, 2), [[0, 2]]);
+        assert_eq!(k_sum(&values, 2, 99_998).len(), 25_000);
+        assert!(k_sum(&values, 2, 99_999).is_empty());
+        assert!(k_sum(&values, 2, 1_000_000).is_empty());
+        assert_eq!(k_sum(&values, 3, 12), [[0, 2, 10], [0, 4, 8], [2, 4, 6]]);
+    }
+
+    #[test]
+    fn no_solution() {
+        let error = product_of_entries(&[1, 2, 3], 2).unwrap_err();
+        assert_eq!(error.to_string(), "no 2 entries sum to 2020");
+
+        let error = product_of_entries(&[-(1 << 40), 2020 + (1 << 40)], 2).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "the product of [-1099511627776, 1099511629796] overflows"
+        );
     }
 }
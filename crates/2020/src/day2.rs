@@ -1,118 +1,527 @@
+use std::{collections::HashMap, fmt, sync::Arc};
+
+use aoc_common::{parse, Answer, ParseError, Shape, Solution};
+use eyre::Result;
 use tracing::debug;
-use std::str::pattern::Pattern;
 
-const INPUT: &str = include_str!("../input2.txt");
+pub fn solve() -> Result<(usize, usize)> {
+    let input = crate::input(2)?;
+    Ok((solve1(&input)?, solve2(&input)?))
+}
+
+pub struct Day2;
 
-pub fn solve() -> eyre::Result<()> {
-    println!("day2.1: {}", solve1(INPUT));
-    println!("day2.2: {}", solve2(INPUT));
-    Ok(())
+impl Solution for Day2 {
+    fn part1(&self, input: &str) -> Result<Answer> {
+        Ok(solve1(input)?.into())
+    }
+    fn part2(&self, input: &str) -> Result<Answer> {
+        Ok(solve2(input)?.into())
+    }
+    fn parse(&self, input: &str) -> Option<Result<()>> {
+        aoc_common::parsed(parse_entries(input))
+    }
+    fn input_shape(&self) -> Option<Shape> {
+        Some(Shape::Lines(r"\d+-\d+ [a-z]: [a-z]+"))
+    }
 }
 
-fn solve1(input: &str) -> String {
-    input
-        .lines()
-        .filter_map(|it| {
-            let valid = PwLine::parse(it);
-            debug!("parsed pw: {:?}", valid);
-            valid
-        })
-        .filter(|it| it.matches_1())
-        .count()
-        .to_string()
+fn solve1(input: &str) -> Result<usize> {
+    count_valid(input, &Count)
+}
+
+fn solve2(input: &str) -> Result<usize> {
+    count_valid(input, &Positions)
 }
 
-fn solve2(input: &str) -> String {
+fn count_valid(input: &str, policy: &dyn Policy) -> Result<usize> {
+    let entries = parse_entries(input)?;
+    Ok(entries
+        .iter()
+        .filter(|entry| policy.check(entry).is_ok())
+        .count())
+}
+
+fn parse_entries(input: &str) -> Result<Vec<Entry<'_>>, ParseError> {
     input
         .lines()
-        .filter_map(|it| {
-            let valid = PwLine::parse(it);
-            debug!("parsed pw: {:?}", valid);
-            valid
-        })
-        .filter(|it| it.matches_2())
-        .count()
-        .to_string()
-}
-
-#[derive(Debug)]
-struct PwLine<'a> {
-    pw: &'a str,
-    lower: usize,
-    upper: usize,
-    which_char: char,
-}
-
-impl<'a> PwLine<'a> {
-    fn parse(line: &'a str) -> Option<Self> {
-        let (num1, line) = parse(line, '-')?;
-        let line = &line[1..];
-        let (num2, line) = parse(line, ' ')?;
-        let line = &line[1..];
-        let (which_char, line) = line.split_at(1);
-        let pw = &line[2..];
-
-        let lower = num1.parse::<usize>().ok()?;
-        let upper = num2.parse::<usize>().ok()?;
-        let which_char = which_char.chars().next()?;
-        Some(PwLine {
-            pw,
-            lower,
-            upper,
-            which_char,
+        .map(Entry::parse)
+        .collect::<Result<_, _>>()
+        .map_err(|e| e.locate(2, input))
+}
+
+/// A password and the rule it was exported with, like `1-3 a: abcde`. What the two numbers
+/// mean is up to the [`Policy`] checking it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Entry<'a> {
+    pub first: usize,
+    pub second: usize,
+    pub letter: char,
+    pub password: &'a str,
+}
+
+impl<'a> Entry<'a> {
+    pub fn parse(line: &'a str) -> Result<Self, ParseError> {
+        let (rule, password) = parse::split_once(line, ": ")?;
+        let (numbers, letter) = parse::split_once(rule, " ")?;
+        let (first, second) = parse::split_once(numbers, "-")?;
+        let mut chars = letter.chars();
+        let letter = match (chars.next(), chars.next()) {
+            (Some(c), None) => c,
+            _ => return Err(ParseError::new(letter, "expected a single letter")),
+        };
+        Ok(Entry {
+            first: parse::number(first)?,
+            second: parse::number(second)?,
+            letter,
+            password,
         })
     }
-    fn matches_1(&self) -> bool {
-        debug!(
-            "pw {:?} must have {}-{} occurences of {:?}",
-            self.pw, self.lower, self.upper, self.which_char
-        );
-        let char_count = self.pw.chars().filter(|c| *c == self.which_char).count();
-        char_count >= self.lower && char_count <= self.upper
-    }
-    fn matches_2(&self) -> bool {
-        let lower = self.lower - 1;
-        let upper = self.upper - 1;
-        if upper >= self.pw.len() {
-            return false;
+}
+
+impl fmt::Display for Entry<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}-{} {}: {}",
+            self.first, self.second, self.letter, self.password
+        )
+    }
+}
+
+/// A way to read an [`Entry`]'s rule.
+pub trait Policy: Send + Sync {
+    /// How the policy is written in an expression for [`Registry::parse`].
+    fn name(&self) -> String;
+
+    /// `Err` with the reason if the password breaks the rule.
+    fn check(&self, entry: &Entry) -> Result<(), String>;
+}
+
+/// The letter occurs at least `first` and at most `second` times, part 1's reading.
+pub struct Count;
+
+impl Policy for Count {
+    fn name(&self) -> String {
+        "count".to_string()
+    }
+    fn check(&self, entry: &Entry) -> Result<(), String> {
+        let count = entry
+            .password
+            .chars()
+            .filter(|&c| c == entry.letter)
+            .count();
+        if (entry.first..=entry.second).contains(&count) {
+            Ok(())
+        } else {
+            Err(format!(
+                "{:?} occurs {} times, not {} to {}",
+                entry.letter, count, entry.first, entry.second
+            ))
         }
-        let char1 = self.pw.chars().nth(lower).unwrap();
-        let char2 = self.pw.chars().nth(upper).unwrap();
-        debug!(
-            "pw {:?} must have {:?} at pos {} XOR at pos {} ({:?}/{:?})",
-            self.pw,
-            self.which_char,
-            lower,
-            upper,
-            self.pw.chars().nth(lower),
-            self.pw.chars().nth(upper),
-        );
-        (self.which_char == char1) ^ (self.which_char == char2)
     }
 }
 
-fn parse<'a, P: Pattern<'a>>(input: &'a str, pat: P) -> Option<(&'a str, &'a str)> {
-    let ix = input.find(pat)?;
-    Some(input.split_at(ix))
+/// The letter is at exactly one of the positions `first` and `second`, which start at 1, part
+/// 2's reading.
+pub struct Positions;
+
+impl Policy for Positions {
+    fn name(&self) -> String {
+        "positions".to_string()
+    }
+    fn check(&self, entry: &Entry) -> Result<(), String> {
+        let at = |position: usize| {
+            position
+                .checked_sub(1)
+                .and_then(|i| entry.password.chars().nth(i))
+                .map(|c| c == entry.letter)
+                .ok_or_else(|| format!("the password has no position {}", position))
+        };
+        match (at(entry.first)?, at(entry.second)?) {
+            (true, false) | (false, true) => Ok(()),
+            (true, true) => Err(format!(
+                "{:?} is at both positions {} and {}",
+                entry.letter, entry.first, entry.second
+            )),
+            (false, false) => Err(format!(
+                "{:?} is at neither position {} nor {}",
+                entry.letter, entry.first, entry.second
+            )),
+        }
+    }
+}
+
+/// Passes if every policy passes.
+pub struct AllOf(pub Vec<Box<dyn Policy>>);
+
+impl Policy for AllOf {
+    fn name(&self) -> String {
+        format!("all({})", names(&self.0))
+    }
+    fn check(&self, entry: &Entry) -> Result<(), String> {
+        let reasons: Vec<String> = self
+            .0
+            .iter()
+            .filter_map(|policy| policy.check(entry).err())
+            .collect();
+        if reasons.is_empty() {
+            Ok(())
+        } else {
+            Err(reasons.join("; "))
+        }
+    }
+}
+
+/// Passes if at least one policy passes.
+pub struct AnyOf(pub Vec<Box<dyn Policy>>);
+
+impl Policy for AnyOf {
+    fn name(&self) -> String {
+        format!("any({})", names(&self.0))
+    }
+    fn check(&self, entry: &Entry) -> Result<(), String> {
+        let mut reasons = Vec::new();
+        for policy in &self.0 {
+            match policy.check(entry) {
+                Ok(()) => return Ok(()),
+                Err(reason) => reasons.push(reason),
+            }
+        }
+        if reasons.is_empty() {
+            Err("no policy to pass".to_string())
+        } else {
+            Err(reasons.join("; "))
+        }
+    }
+}
+
+/// Passes if the policy fails.
+pub struct Not(pub Box<dyn Policy>);
+
+impl Policy for Not {
+    fn name(&self) -> String {
+        format!("not({})", self.0.name())
+    }
+    fn check(&self, entry: &Entry) -> Result<(), String> {
+        match self.0.check(entry) {
+            Ok(()) => Err(format!("passes {}", self.0.name())),
+            Err(_) => Ok(()),
+        }
+    }
+}
+
+fn names(policies: &[Box<dyn Policy>]) -> String {
+    let names: Vec<String> = policies.iter().map(|policy| policy.name()).collect();
+    names.join(", ")
+}
+
+/// A policy of a [`Registry`], named as it was registered.
+struct Named {
+    name: String,
+    policy: Arc<dyn Policy>,
+}
+
+impl Policy for Named {
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+    fn check(&self, entry: &Entry) -> Result<(), String> {
+        self.policy.check(entry)
+    }
+}
+
+/// Policies by name, to combine in expressions like `all(count, not(positions))`.
+pub struct Registry {
+    policies: HashMap<String, Arc<dyn Policy>>,
+}
+
+impl Default for Registry {
+    /// The puzzle's [`Count`] and [`Positions`].
+    fn default() -> Self {
+        let policies: Vec<(&str, Arc<dyn Policy>)> = vec![
+            ("count", Arc::new(Count)),
+            ("positions", Arc::new(Positions)),
+        ];
+        Registry {
+            policies: policies
+                .into_iter()
+                .map(|(name, policy)| (name.to_string(), policy))
+                .collect(),
+        }
+    }
+}
+
+impl Registry {
+    pub fn empty() -> Self {
+        Registry {
+            policies: HashMap::new(),
+        }
+    }
+
+    /// Adds a policy, replacing one with the same name. Names are made of letters, digits, `_`
+    /// and `-`, and can't be `all`, `any` or `not`.
+    pub fn register(&mut self, name: &str, policy: impl Policy + 'static) -> Result<(), String> {
+        if !is_name(name) || ["all", "any", "not"].contains(&name) {
+            return Err(format!("invalid policy name {:?}", name));
+        }
+        self.policies.insert(name.to_string(), Arc::new(policy));
+        Ok(())
+    }
+
+    /// The registered names in order.
+    pub fn names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.policies.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        names
+    }
+
+    /// A policy made of registered names and `all(..)`, `any(..)` and `not(..)`.
+    pub fn parse(&self, expression: &str) -> Result<Box<dyn Policy>, ParseError> {
+        let (policy, rest) = self.parse_policy(expression)?;
+        let rest = rest.trim_start();
+        if !rest.is_empty() {
+            return Err(ParseError::new(rest, "expected the end of the policy"));
+        }
+        Ok(policy)
+    }
+
+    /// The policy at the start of `text`, and what's left after it.
+    fn parse_policy<'a>(&self, text: &'a str) -> Result<(Box<dyn Policy>, &'a str), ParseError> {
+        let text = text.trim_start();
+        let end = text.find(|c: char| !is_name_char(c)).unwrap_or(text.len());
+        let (name, rest) = text.split_at(end);
+        if name.is_empty() {
+            return Err(ParseError::new(
+                &text[..text.chars().next().map_or(0, char::len_utf8)],
+                "expected a policy",
+            ));
+        }
+        if !["all", "any", "not"].contains(&name) {
+            let policy = self.policies.get(name).ok_or_else(|| {
+                ParseError::new(name, format!("expected one of {}", self.names().join(", ")))
+            })?;
+            let policy = Named {
+                name: name.to_string(),
+                policy: Arc::clone(policy),
+            };
+            return Ok((Box::new(policy), rest));
+        }
+
+        let mut rest = parse::strip_prefix(rest.trim_start(), "(")?;
+        let mut policies = Vec::new();
+        loop {
+            let (policy, after) = self.parse_policy(rest)?;
+            policies.push(policy);
+            rest = after.trim_start();
+            match rest.strip_prefix(',') {
+                Some(after) => rest = after,
+                None => break,
+            }
+        }
+        let rest = parse::strip_prefix(rest, ")")?;
+        let policy: Box<dyn Policy> = match name {
+            "all" => Box::new(AllOf(policies)),
+            "any" => Box::new(AnyOf(policies)),
+            _ if policies.len() == 1 => Box::new(Not(policies.remove(0))),
+            _ => {
+                let expression = &text[..text.len() - rest.len()];
+                return Err(ParseError::new(
+                    expression,
+                    "expected a single policy in not(..)",
+                ));
+            }
+        };
+        Ok((policy, rest))
+    }
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_' || c == '-'
+}
+
+fn is_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(is_name_char)
+}
+
+/// An entry that breaks the policy of an [`audit`], or that can't be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Failure {
+    /// Starts at 1.
+    pub line: usize,
+    pub text: String,
+    pub reason: String,
+}
+
+/// What [`audit`] found.
+pub struct Audit {
+    pub policy: String,
+    pub entries: usize,
+    pub failures: Vec<Failure>,
+}
+
+/// Checks every line of `input` against `policy`. Lines that can't be parsed fail as well, so
+/// a messy export doesn't hide entries.
+pub fn audit(input: &str, policy: &dyn Policy) -> Audit {
+    let mut failures = Vec::new();
+    for (i, line) in input.lines().enumerate() {
+        let reason = match Entry::parse(line) {
+            Ok(entry) => match policy.check(&entry) {
+                Ok(()) => continue,
+                Err(reason) => reason,
+            },
+            Err(e) => format!("can't be parsed, {}, found {:?}", e.reason, e.text),
+        };
+        debug!("line {} fails: {}", i + 1, reason);
+        failures.push(Failure {
+            line: i + 1,
+            text: line.to_string(),
+            reason,
+        });
+    }
+    Audit {
+        policy: policy.name(),
+        entries: input.lines().count(),
+        failures,
+    }
+}
+
+impl fmt::Display for Audit {
+    /// A line for each failure, then a summary.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for failure in &self.failures {
+            writeln!(
+                f,
+                "line {}: {}: {}",
+                failure.line, failure.text, failure.reason
+            )?;
+        }
+        write!(
+            f,
+            "{} of {} entries pass {}",
+            self.entries - self.failures.len(),
+            self.entries,
+            self.policy
+        )
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    /// Its answers for both parts are checked by `aoc --examples`.
+    const EXAMPLE: &str = include_str!("../../../examples/2020/day2/example.txt");
+
     #[test]
-    fn pt1_ex() {
-        assert_eq!(solve1("1-3 a: abcde\n1-3 b: cdefg\n2-9 c: ccccccccc",), "2");
+    fn day2() {
+        let input = crate::input(2).unwrap();
+        assert_eq!(solve1(&input).unwrap(), 638);
+        assert_eq!(solve2(&input).unwrap(), 699);
     }
 
     #[test]
-    fn pt2_ex() {
-        assert_eq!(solve2("1-3 a: abcde\n1-3 b: cdefg\n2-9 c: ccccccccc",), "1");
+    fn entries() {
+        let entry = Entry::parse("2-9 c: ccccccccc").unwrap();
+        assert_eq!(
+            entry,
+            Entry {
+                first: 2,
+                second: 9,
+                letter: 'c',
+                password: "ccccccccc"
+            }
+        );
+        assert_eq!(entry.to_string(), "2-9 c: ccccccccc");
+        assert_eq!(
+            parse_entries("1-3 a: abcde\n1-x b: cdefg\n")
+                .unwrap_err()
+                .to_string(),
+            r#"day 2, line 2, column 3: invalid digit found in string, found "x""#
+        );
+        assert_eq!(
+            Entry::parse("1-3 ab: abcde").unwrap_err().reason,
+            "expected a single letter"
+        );
     }
 
     #[test]
-    fn day2() {
-        assert_eq!(solve1(INPUT), "638");
-        assert_eq!(solve2(INPUT), "699");
+    fn puzzle_policies() {
+        let check = |policy: &dyn Policy, line| policy.check(&Entry::parse(line).unwrap());
+        assert_eq!(
+            check(&Count, "1-3 b: cdefg"),
+            Err("'b' occurs 0 times, not 1 to 3".to_string())
+        );
+        assert_eq!(
+            check(&Positions, "2-9 c: ccccccccc"),
+            Err("'c' is at both positions 2 and 9".to_string())
+        );
+        assert_eq!(
+            check(&Positions, "1-3 b: cdefg"),
+            Err("'b' is at neither position 1 nor 3".to_string())
+        );
+        assert_eq!(
+            check(&Positions, "1-9 a: abc"),
+            Err("the password has no position 9".to_string())
+        );
+        assert_eq!(
+            check(&Positions, "0-1 a: abc"),
+            Err("the password has no position 0".to_string())
+        );
+    }
+
+    #[test]
+    fn expressions() {
+        let registry = Registry::default();
+        let policy = registry.parse(" all(count , not( positions))").unwrap();
+        assert_eq!(policy.name(), "all(count, not(positions))");
+        assert_eq!(count_valid(EXAMPLE, &*policy).unwrap(), 1);
+        let policy = registry.parse("any(count,positions)").unwrap();
+        assert_eq!(count_valid(EXAMPLE, &*policy).unwrap(), 2);
+
+        let error = |expression| registry.parse(expression).err().unwrap().reason;
+        assert_eq!(error("length"), "expected one of count, positions");
+        assert_eq!(error("all(count"), r#"expected ")""#);
+        assert_eq!(
+            error("not(count, positions)"),
+            "expected a single policy in not(..)"
+        );
+        assert_eq!(error("any()"), "expected a policy");
+        assert_eq!(error("count positions"), "expected the end of the policy");
+    }
+
+    #[test]
+    fn custom_policies() {
+        struct MinLength(usize);
+        impl Policy for MinLength {
+            fn name(&self) -> String {
+                format!("min-length-{}", self.0)
+            }
+            fn check(&self, entry: &Entry) -> Result<(), String> {
+                match entry.password.chars().count() {
+                    n if n < self.0 => Err(format!("has {} characters, not {}", n, self.0)),
+                    _ => Ok(()),
+                }
+            }
+        }
+
+        let mut registry = Registry::default();
+        registry.register("long", MinLength(6)).unwrap();
+        assert_eq!(
+            registry.register("not", MinLength(1)),
+            Err(r#"invalid policy name "not""#.to_string())
+        );
+        assert!(registry.register("min length", MinLength(1)).is_err());
+        assert_eq!(registry.names(), ["count", "long", "positions"]);
+        let policy = registry.parse("all(count, long)").unwrap();
+        let input = format!("{}abc\n", EXAMPLE);
+        assert_eq!(
+            audit(&input, &*policy).to_string(),
+            "\
+line 1: 1-3 a: abcde: has 5 characters, not 6
+line 2: 1-3 b: cdefg: 'b' occurs 0 times, not 1 to 3; has 5 characters, not 6
+line 4: abc: can't be parsed, expected \": \", found \"abc\"
+1 of 4 entries pass all(count, long)"
+        );
     }
 }
@@ -1,52 +1,256 @@
 use std::iter::successors;
 
-const INPUT: &str = include_str!("../input3.txt");
+use aoc_common::{Answer, Coord, Grid, ParseError, Shape, Solution};
+use eyre::{ensure, Result};
 
-pub fn solve() -> eyre::Result<()> {
-    println!("day3.1: {}", solve1(INPUT));
-    println!("day3.2: {}", solve2(INPUT));
-    Ok(())
+pub fn solve() -> Result<(usize, usize)> {
+    let input = crate::input(3)?;
+    Ok((solve1(&input)?, solve2(&input)?))
 }
 
-fn solve1(input: &str) -> usize {
-    let field = parse_field(input);
+pub struct Day3;
 
-    count_path_tree_collisions(&field, (1, 3))
+impl Solution for Day3 {
+    fn part1(&self, input: &str) -> Result<Answer> {
+        Ok(solve1(input)?.into())
+    }
+    fn part2(&self, input: &str) -> Result<Answer> {
+        Ok(solve2(input)?.into())
+    }
+    fn input_shape(&self) -> Option<Shape> {
+        Some(Shape::Grid {
+            cell: "[.#]",
+            size: None,
+        })
+    }
 }
 
-fn solve2(input: &str) -> usize {
-    let field = parse_field(input);
-    [(1, 1), (1, 3), (1, 5), (1, 7), (2, 1)]
+/// Right 3, down 1.
+const SLOPE: Coord = Coord::new(3, 1);
+
+const SLOPES: [Coord; 5] = [
+    Coord::new(1, 1),
+    Coord::new(3, 1),
+    Coord::new(5, 1),
+    Coord::new(7, 1),
+    Coord::new(1, 2),
+];
+
+fn solve1(input: &str) -> Result<usize> {
+    let forest = Forest::parse(input)?;
+    Ok(forest.ride(SLOPE, Wrap::Horizontal)?.collisions())
+}
+
+fn solve2(input: &str) -> Result<usize> {
+    let forest = Forest::parse(input)?;
+    Ok(forest
+        .rides(&SLOPES, Wrap::Horizontal)?
         .iter()
-        .map(|step| count_path_tree_collisions(&field, *step))
-        .product()
+        .map(Ride::collisions)
+        .product())
+}
+
+/// Which edges of the map it repeats beyond.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Wrap {
+    /// To the left and right, the puzzle's map. A ride ends when it leaves the map at the top
+    /// or bottom.
+    Horizontal,
+    /// In every direction. A ride ends before it gets back to where it started.
+    Both,
+}
+
+/// The trees of the map, `true` where there is one.
+pub struct Forest {
+    trees: Grid<bool>,
 }
 
-fn parse_field(input: &str) -> Vec<Vec<char>> {
-    input
-        .lines()
-        .map(|line| line.chars().collect::<Vec<_>>())
-        .collect::<Vec<_>>()
+/// The ride down one slope from the top left.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ride {
+    pub slope: Coord,
+    /// The trees hit in order, as coordinates on the map.
+    pub trees: Vec<Coord>,
 }
 
-fn count_path_tree_collisions(field: &[Vec<char>], step: (usize, usize)) -> usize {
-    successors(Some((0, 0)), |(y, x)| Some((y + step.0, x + step.1)))
-        .take_while(|(y, _)| *y < field.len())
-        .map(|(y, x)| {
-            let line = &field[y];
-            line[x % line.len()]
+impl Ride {
+    pub fn collisions(&self) -> usize {
+        self.trees.len()
+    }
+}
+
+impl Forest {
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let trees = Grid::parse(input, |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        })
+        .map_err(|e| e.locate(3, input))?;
+        Ok(Forest { trees })
+    }
+
+    /// The coordinates on the map passed when going `slope.x` to the right and `slope.y` down
+    /// at a time, starting at the top left. Negative steps go left or up. Fails for a
+    /// horizontal slope that only wraps horizontally, as it would never leave the map.
+    pub fn path(&self, slope: Coord, wrap: Wrap) -> Result<impl Iterator<Item = Coord>> {
+        ensure!(
+            slope.y != 0 || wrap == Wrap::Both,
+            "the horizontal slope {:?} never leaves the map",
+            slope
+        );
+        let (width, height) = (self.trees.width() as i64, self.trees.height() as i64);
+        let start = Coord::new(0, 0);
+        let first = (width > 0 && height > 0).then_some(start);
+        Ok(successors(first, move |&coord| {
+            let next = coord + slope;
+            let next = Coord::new(next.x.rem_euclid(width), next.y);
+            match wrap {
+                Wrap::Horizontal => (0..height).contains(&next.y).then_some(next),
+                Wrap::Both => Some(Coord::new(next.x, next.y.rem_euclid(height)))
+                    .filter(|&next| next != start),
+            }
+        }))
+    }
+
+    /// Goes down `slope` and notes the trees hit.
+    pub fn ride(&self, slope: Coord, wrap: Wrap) -> Result<Ride> {
+        Ok(Ride {
+            slope,
+            trees: self
+                .path(slope, wrap)?
+                .filter(|&coord| self.trees[coord])
+                .collect(),
         })
-        .filter(|c| *c == '#')
-        .count()
+    }
+
+    /// A [`ride`](Forest::ride) for every slope, in the same order.
+    pub fn rides(&self, slopes: &[Coord], wrap: Wrap) -> Result<Vec<Ride>> {
+        slopes.iter().map(|&slope| self.ride(slope, wrap)).collect()
+    }
+
+    /// Rides every slope of at most `max.x` to either side and `max.y` up or down, and orders
+    /// them by fewest collisions, then by slope. Without vertical wrapping only slopes going
+    /// down are ridden, as the others leave the map right away or never.
+    pub fn rank_slopes(&self, max: Coord, wrap: Wrap) -> Result<Vec<Ride>> {
+        let slopes: Vec<Coord> = (-max.y..=max.y)
+            .flat_map(|y| (-max.x..=max.x).map(move |x| Coord::new(x, y)))
+            .filter(|slope| match wrap {
+                Wrap::Horizontal => slope.y > 0,
+                Wrap::Both => *slope != Coord::new(0, 0),
+            })
+            .collect();
+        let mut rides = self.rides(&slopes, wrap)?;
+        rides.sort_by_key(|ride| (ride.collisions(), ride.slope));
+        Ok(rides)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    const EXAMPLE: &str = include_str!("../../../examples/2020/day3/example.txt");
+
     #[test]
     fn day3() {
-        assert_eq!(solve1(INPUT), 292);
-        assert_eq!(solve2(INPUT), 9354744432);
+        let input = crate::input(3).unwrap();
+        assert_eq!(solve1(&input).unwrap(), 292);
+        assert_eq!(solve2(&input).unwrap(), 9354744432);
+    }
+
+    #[test]
+    fn example() {
+        assert_eq!(solve1(EXAMPLE).unwrap(), 7);
+        assert_eq!(solve2(EXAMPLE).unwrap(), 336);
+        let forest = Forest::parse(EXAMPLE).unwrap();
+        let rides = forest.rides(&SLOPES, Wrap::Horizontal).unwrap();
+        let collisions: Vec<usize> = rides.iter().map(Ride::collisions).collect();
+        assert_eq!(collisions, [2, 7, 3, 4, 2]);
+        assert_eq!(
+            rides[1].trees,
+            [
+                Coord::new(6, 2),
+                Coord::new(1, 4),
+                Coord::new(4, 5),
+                Coord::new(10, 7),
+                Coord::new(2, 8),
+                Coord::new(5, 9),
+                Coord::new(8, 10),
+            ]
+        );
+        assert_eq!(
+            Forest::parse("..#\n.x.\n").err().unwrap().to_string(),
+            r#"day 3, line 2, column 2: invalid cell, found "x""#
+        );
+    }
+
+    #[test]
+    fn wrapping() {
+        let forest = Forest::parse("#..\n.#.\n").unwrap();
+        let path = |slope, wrap| forest.path(slope, wrap).unwrap().collect::<Vec<_>>();
+        assert_eq!(
+            path(Coord::new(-1, 1), Wrap::Horizontal),
+            [Coord::new(0, 0), Coord::new(2, 1)]
+        );
+        assert_eq!(
+            path(Coord::new(1, -1), Wrap::Horizontal),
+            [Coord::new(0, 0)]
+        );
+        // Every cell of the 3x2 map is on the path before it gets back to the start.
+        assert_eq!(
+            path(Coord::new(1, 1), Wrap::Both),
+            [
+                Coord::new(0, 0),
+                Coord::new(1, 1),
+                Coord::new(2, 0),
+                Coord::new(0, 1),
+                Coord::new(1, 0),
+                Coord::new(2, 1),
+            ]
+        );
+        assert_eq!(
+            path(Coord::new(0, -1), Wrap::Both),
+            [Coord::new(0, 0), Coord::new(0, 1)]
+        );
+        assert_eq!(path(Coord::new(1, 0), Wrap::Both).len(), 3);
+        assert_eq!(
+            forest
+                .path(Coord::new(-1, 0), Wrap::Horizontal)
+                .err()
+                .unwrap()
+                .to_string(),
+            "the horizontal slope (-1,0) never leaves the map"
+        );
+        let collisions = |slope| forest.ride(slope, Wrap::Both).unwrap().collisions();
+        assert_eq!(collisions(Coord::new(1, 1)), 2);
+        assert_eq!(collisions(Coord::new(0, 1)), 1);
+    }
+
+    #[test]
+    fn ranking() {
+        let forest = Forest::parse(EXAMPLE).unwrap();
+        let ranked = forest
+            .rank_slopes(Coord::new(3, 2), Wrap::Horizontal)
+            .unwrap();
+        assert_eq!(ranked.len(), 7 * 2);
+        assert!(ranked
+            .windows(2)
+            .all(|pair| pair[0].collisions() <= pair[1].collisions()));
+        let best: Vec<(Coord, usize)> = ranked[..3]
+            .iter()
+            .map(|ride| (ride.slope, ride.collisions()))
+            .collect();
+        assert_eq!(
+            best,
+            [
+                (Coord::new(-3, 2), 1),
+                (Coord::new(0, 2), 1),
+                (Coord::new(2, 1), 1)
+            ]
+        );
+
+        let ranked = forest.rank_slopes(Coord::new(1, 1), Wrap::Both).unwrap();
+        assert_eq!(ranked.len(), 8);
     }
 }
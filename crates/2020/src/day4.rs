@@ -1,135 +1,236 @@
-use eyre::Result;
-use tracing::debug;
+use std::{collections::BTreeMap, fmt, fs, path::Path};
+
+use aoc_common::{parse, Answer, ParseError, Shape, Solution};
+use eyre::{Result, WrapErr};
 use once_cell::sync::Lazy;
 use regex::Regex;
-use std::collections::HashMap;
+use serde::{Deserialize, Deserializer};
+use tracing::debug;
 
-const INPUT: &str = include_str!("../input4.txt");
+pub fn solve() -> Result<(usize, usize)> {
+    let input = crate::input(4)?;
+    Ok((solve1(&input)?, solve2(&input)?))
+}
+
+pub struct Day4;
 
-pub fn solve() -> Result<()> {
-    println!("day4.1: {}", solve1(INPUT));
-    println!("day4.2: {}", solve2(INPUT));
-    Ok(())
+impl Solution for Day4 {
+    fn part1(&self, input: &str) -> Result<Answer> {
+        Ok(solve1(input)?.into())
+    }
+    fn part2(&self, input: &str) -> Result<Answer> {
+        Ok(solve2(input)?.into())
+    }
+    fn input_shape(&self) -> Option<Shape> {
+        Some(Shape::Blocks(&[Shape::Lines(
+            r"[a-z]{3}:\S+( [a-z]{3}:\S+)*",
+        )]))
+    }
 }
 
-fn solve1(input: &str) -> usize {
-    let passports = input.split("\n\n");
-    passports
-        .map(parse_passport)
-        .filter(|it| it.valid1())
-        .count()
+/// The rules of `day4.toml`.
+pub static PASSPORT: Lazy<Schema> =
+    Lazy::new(|| Schema::parse(include_str!("day4.toml")).expect("the passport schema is valid"));
+
+fn solve1(input: &str) -> Result<usize> {
+    Ok(parse_records(input)?
+        .iter()
+        .filter(|passport| {
+            PASSPORT
+                .validate(passport)
+                .iter()
+                .all(|violation| !matches!(violation, Violation::Missing { .. }))
+        })
+        .count())
 }
 
-fn solve2(input: &str) -> usize {
-    let passports = input.split("\n\n");
-    passports
-        .map(parse_passport)
-        .filter(|it| it.valid2())
-        .count()
+fn solve2(input: &str) -> Result<usize> {
+    Ok(parse_records(input)?
+        .iter()
+        .filter(|passport| PASSPORT.validate(passport).is_empty())
+        .count())
 }
 
-fn parse_passport(passport_lines: &str) -> Passport {
-    let mut fields = passport_lines
-        .split(char::is_whitespace)
-        .filter_map(|field| {
-            let split = field.trim().split(':').collect::<Vec<_>>();
-            Some((split.get(0)?.to_string(), split.get(1)?.to_string()))
+/// The `key:value` fields of a record.
+pub type Record<'a> = BTreeMap<&'a str, &'a str>;
+
+/// Records separated by blank lines, whose fields are separated by spaces or line breaks.
+pub fn parse_records(input: &str) -> Result<Vec<Record<'_>>, ParseError> {
+    input
+        .split("\n\n")
+        .map(|block| {
+            block
+                .split_whitespace()
+                .map(|field| parse::split_once(field, ":"))
+                .collect()
         })
-        .collect::<HashMap<_, _>>();
-    let passport = Passport {
-        byr: fields.remove("byr"),
-        iyr: fields.remove("iyr"),
-        eyr: fields.remove("eyr"),
-        hgt: fields.remove("hgt"),
-        hcl: fields.remove("hcl"),
-        ecl: fields.remove("ecl"),
-        pid: fields.remove("pid"),
-        // cid: fields.remove("cid"),
-    };
-    debug!("Created passport {:?}", passport);
-    passport
+        .collect::<Result<_, _>>()
+        .map_err(|e| e.locate(4, input))
 }
 
+/// The fields a record needs, by key. Keys it doesn't know are ignored.
+#[derive(Debug, Deserialize)]
+pub struct Schema {
+    pub fields: BTreeMap<String, Field>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Field {
+    /// Required fields are reported as missing.
+    #[serde(default)]
+    pub optional: bool,
+    #[serde(flatten)]
+    pub rule: Rule,
+}
+
+/// What a field's value has to look like, written as `kind = "..."` in a schema.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+pub enum Rule {
+    /// A whole number from `min` to `max`.
+    Range { min: i64, max: i64 },
+    /// A whole number directly followed by one of the units, each with its own `[min, max]`.
+    Units { units: BTreeMap<String, [i64; 2]> },
+    /// Matches the pattern as a whole.
+    Regex { pattern: Pattern },
+    /// One of the values.
+    OneOf { values: Vec<String> },
+    /// Exactly `length` ASCII digits.
+    Digits { length: usize },
+    /// Anything.
+    Any,
+}
+
+/// A regex that has to match a whole value.
 #[derive(Debug)]
-struct Passport {
-    byr: Option<String>,
-    iyr: Option<String>,
-    eyr: Option<String>,
-    hgt: Option<String>,
-    hcl: Option<String>,
-    ecl: Option<String>,
-    pid: Option<String>,
-    // cid: Option<String>,
-}
-
-impl Passport {
-    fn valid1(&self) -> bool {
-        let valid = [
-            &self.byr, &self.iyr, &self.eyr, &self.hgt, &self.hcl, &self.ecl, &self.pid,
-        ]
-        .iter()
-        .all(|it| it.is_some());
-        debug!("{} {:?}", if valid { "Valid" } else { "Invalid" }, self);
-        valid
+pub struct Pattern {
+    source: String,
+    regex: Regex,
+}
+
+impl<'de> Deserialize<'de> for Pattern {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let source = String::deserialize(deserializer)?;
+        let regex = Regex::new(&format!("^(?:{})$", source)).map_err(serde::de::Error::custom)?;
+        Ok(Pattern { source, regex })
     }
+}
 
-    fn valid2(&self) -> bool {
-        static HAIR_COLOR_RE: Lazy<Regex> = Lazy::new(|| Regex::new("#[0-9a-f]{6}").unwrap());
+/// Why a record doesn't follow its [`Schema`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Violation {
+    Missing {
+        field: String,
+    },
+    Invalid {
+        field: String,
+        value: String,
+        reason: String,
+    },
+}
 
-        let valid = [
-            number_between(self.byr.as_deref(), 1920, 2002),
-            number_between(self.iyr.as_deref(), 2010, 2020),
-            number_between(self.eyr.as_deref(), 2020, 2030),
-            valid_cm(self.hgt.as_deref()) || valid_inch(self.hgt.as_deref()),
-            regex_matches(self.hcl.as_deref(), &*HAIR_COLOR_RE),
-            ["amb", "blu", "brn", "gry", "grn", "hzl", "oth"]
-                .iter()
-                .any(|it| Some(*it) == self.ecl.as_deref()),
-            match &self.pid {
-                Some(pid) => pid.len() == 9 && pid.chars().all(|c| c.is_numeric()),
-                None => false,
-            },
-        ]
-        .iter()
-        .all(|b| *b);
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Violation::Missing { field } => write!(f, "{} is missing", field),
+            Violation::Invalid {
+                field,
+                value,
+                reason,
+            } => write!(f, "{}:{} {}", field, value, reason),
+        }
+    }
+}
 
-        debug!("{} {:?}", if valid { "Valid" } else { "Invalid" }, self);
+impl Schema {
+    pub fn parse(toml: &str) -> Result<Self> {
+        toml::from_str(toml).wrap_err("invalid schema")
+    }
 
-        valid
+    pub fn load(path: &Path) -> Result<Self> {
+        let toml = fs::read_to_string(path)
+            .wrap_err_with(|| format!("couldn't read {}", path.display()))?;
+        Schema::parse(&toml).wrap_err_with(|| format!("in {}", path.display()))
     }
-}
 
-fn valid_cm(height: Option<&str>) -> bool {
-    let height = match height {
-        Some(it) => it,
-        None => return false,
-    };
-    static CM_RE: Lazy<Regex> = Lazy::new(|| Regex::new("[0-9]{3}cm").unwrap());
-    CM_RE.is_match(height) && number_between(Some(&height[..3]), 150, 193)
+    /// Everything wrong with `record`, by field. Empty if it's valid.
+    pub fn validate(&self, record: &Record) -> Vec<Violation> {
+        let violations: Vec<Violation> = self
+            .fields
+            .iter()
+            .filter_map(|(key, field)| match record.get(key.as_str()) {
+                None if field.optional => None,
+                None => Some(Violation::Missing { field: key.clone() }),
+                Some(value) => field
+                    .rule
+                    .check(value)
+                    .err()
+                    .map(|reason| Violation::Invalid {
+                        field: key.clone(),
+                        value: value.to_string(),
+                        reason,
+                    }),
+            })
+            .collect();
+        debug!("{:?}: {:?}", record, violations);
+        violations
+    }
 }
 
-fn valid_inch(height: Option<&str>) -> bool {
-    let height = match height {
-        Some(it) => it,
-        None => return false,
-    };
-    static INCH_RE: Lazy<Regex> = Lazy::new(|| Regex::new("[0-9]{2}in").unwrap());
-    INCH_RE.is_match(height) && number_between(Some(&height[..2]), 59, 76)
+impl Rule {
+    /// `Err` with the reason if `value` breaks the rule.
+    pub fn check(&self, value: &str) -> Result<(), String> {
+        match self {
+            Rule::Range { min, max } => {
+                let number = whole_number(value).ok_or("is not a whole number")?;
+                in_range(number, *min, *max, "")
+            }
+            Rule::Units { units } => {
+                let split = value
+                    .find(|c: char| !c.is_ascii_digit())
+                    .unwrap_or(value.len());
+                let (number, unit) = value.split_at(split);
+                let names = || units.keys().cloned().collect::<Vec<_>>().join(", ");
+                let [min, max] = units
+                    .get(unit)
+                    .ok_or_else(|| format!("has no unit of {}", names()))?;
+                let number = whole_number(number).ok_or("has no number before its unit")?;
+                in_range(number, *min, *max, unit)
+            }
+            Rule::Regex { pattern } if pattern.regex.is_match(value) => Ok(()),
+            Rule::Regex { pattern } => Err(format!("doesn't match /{}/", pattern.source)),
+            Rule::OneOf { values } if values.iter().any(|it| it == value) => Ok(()),
+            Rule::OneOf { values } => Err(format!("is not one of {}", values.join(", "))),
+            Rule::Digits { length }
+                if value.len() == *length && value.bytes().all(|b| b.is_ascii_digit()) =>
+            {
+                Ok(())
+            }
+            Rule::Digits { length } => Err(format!("is not {} digits", length)),
+            Rule::Any => Ok(()),
+        }
+    }
 }
 
-fn number_between(num: Option<&str>, min: u32, max: u32) -> bool {
-    let valid: Option<bool> = try {
-        let num: u32 = num.as_ref()?.parse().ok()?;
-        num >= min && num <= max
-    };
-    debug!("{:?} between {} and {}: {:?}", num, min, max, valid);
-    valid.unwrap_or(false)
+/// Only digits, with a `-` for negative numbers.
+fn whole_number(text: &str) -> Option<i64> {
+    let digits = text.strip_prefix('-').unwrap_or(text);
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    text.parse().ok()
 }
 
-fn regex_matches(s: Option<&str>, regex: &Regex) -> bool {
-    match s {
-        Some(s) => regex.is_match(s),
-        None => false,
+fn in_range(number: i64, min: i64, max: i64, unit: &str) -> Result<(), String> {
+    if (min..=max).contains(&number) {
+        Ok(())
+    } else {
+        Err(format!(
+            "is not from {}{unit} to {}{unit}",
+            min,
+            max,
+            unit = unit
+        ))
     }
 }
 
@@ -139,63 +240,112 @@ mod tests {
 
     #[test]
     fn day4() {
-        assert_eq!(solve1(INPUT), 256);
-        assert_eq!(solve2(INPUT), 198);
+        let input = crate::input(4).unwrap();
+        assert_eq!(solve1(&input).unwrap(), 256);
+        assert_eq!(solve2(&input).unwrap(), 198);
     }
 
-    #[test]
-    fn pt1_ex() {
-        let input = "ecl:gry pid:860033327 eyr:2020 hcl:#fffffd
-byr:1937 iyr:2017 cid:147 hgt:183cm
-
-iyr:2013 ecl:amb cid:350 eyr:2023 pid:028048884
-hcl:#cfa07d byr:1929
-
-hcl:#ae17e1 iyr:2013
-eyr:2024
-ecl:brn pid:760753108 byr:1931
-hgt:179cm
-
-hcl:#cfa07d eyr:2025 pid:166559648
-iyr:2011 ecl:brn hgt:59in";
-
-        assert_eq!(solve1(input), 2);
+    fn violations(record: &str) -> Vec<String> {
+        let records = parse_records(record).unwrap();
+        PASSPORT
+            .validate(&records[0])
+            .iter()
+            .map(ToString::to_string)
+            .collect()
     }
 
     #[test]
-    fn pt2_ex_invalid() {
-        let invalid = "eyr:1972 cid:100
-hcl:#18171d ecl:amb hgt:170 pid:186cm iyr:2018 byr:1926
-
-iyr:2019
-hcl:#602927 eyr:1967 hgt:170cm
-ecl:grn pid:012533040 byr:1946
+    fn passports() {
+        assert!(violations(
+            "pid:087499704 hgt:74in ecl:grn iyr:2012 eyr:2030 byr:1980\nhcl:#623a2f"
+        )
+        .is_empty());
+        assert_eq!(
+            violations("eyr:1972 cid:100\nhcl:#18171d ecl:amb hgt:170 pid:186cm iyr:2018"),
+            [
+                "byr is missing",
+                "eyr:1972 is not from 2020 to 2030",
+                "hgt:170 has no unit of cm, in",
+                "pid:186cm is not 9 digits",
+            ]
+        );
+        assert_eq!(
+            violations("hgt:59cm ecl:zzz eyr:2038 hcl:74454a iyr:2023 pid:3556412378 byr:2007"),
+            [
+                "byr:2007 is not from 1920 to 2002",
+                "ecl:zzz is not one of amb, blu, brn, gry, grn, hzl, oth",
+                "eyr:2038 is not from 2020 to 2030",
+                "hcl:74454a doesn't match /#[0-9a-f]{6}/",
+                "hgt:59cm is not from 150cm to 193cm",
+                "iyr:2023 is not from 2010 to 2020",
+                "pid:3556412378 is not 9 digits",
+            ]
+        );
+    }
 
-hcl:dab227 iyr:2012
-ecl:brn hgt:182cm pid:021572410 eyr:2020 byr:1992 cid:277
+    #[test]
+    fn rules() {
+        let Field { rule, .. } = &PASSPORT.fields["hcl"];
+        // Only whole values match.
+        assert!(rule.check("#123abc").is_ok());
+        assert!(rule.check("#123abcd").is_err());
+        assert!(rule.check("x#123abc").is_err());
 
-hgt:59cm ecl:zzz
-eyr:2038 hcl:74454a iyr:2023
-pid:3556412378 byr:2007";
+        let Field { rule, .. } = &PASSPORT.fields["hgt"];
+        assert!(rule.check("190cm").is_ok());
+        assert!(rule.check("1190cm").is_err());
+        assert_eq!(
+            rule.check("cm"),
+            Err("has no number before its unit".into())
+        );
+        assert_eq!(rule.check("60ft"), Err("has no unit of cm, in".into()));
 
-        assert_eq!(solve2(invalid), 0);
+        let Field { rule, .. } = &PASSPORT.fields["byr"];
+        assert_eq!(rule.check("+1990"), Err("is not a whole number".into()));
+        let Field { rule, .. } = &PASSPORT.fields["pid"];
+        assert!(rule.check("000000001").is_ok());
     }
 
     #[test]
-    fn pt2_ex_valid() {
-        let valid = "pid:087499704 hgt:74in ecl:grn iyr:2012 eyr:2030 byr:1980
-hcl:#623a2f
-
-eyr:2029 ecl:blu cid:129 byr:1989
-iyr:2014 pid:896056539 hcl:#a97842 hgt:165cm
+    fn schemas() {
+        let schema = Schema::parse(
+            r#"
+            [fields.id]
+            kind = "digits"
+            length = 3
 
-hcl:#888785
-hgt:164cm byr:2001 iyr:2015 cid:88
-pid:545766238 ecl:hzl
-eyr:2022
+            [fields.temperature]
+            kind = "range"
+            min = -40
+            max = 50
+            optional = true
+            "#,
+        )
+        .unwrap();
+        let records = parse_records("id:007 temperature:-41\n\nname:x").unwrap();
+        assert_eq!(
+            schema.validate(&records[0]),
+            [Violation::Invalid {
+                field: "temperature".into(),
+                value: "-41".into(),
+                reason: "is not from -40 to 50".into(),
+            }]
+        );
+        assert_eq!(
+            schema.validate(&records[1]),
+            [Violation::Missing { field: "id".into() }]
+        );
 
-iyr:2010 hgt:158cm hcl:#b6652a ecl:blu byr:1944 eyr:2021 pid:093154719
-";
-        assert_eq!(solve2(valid), 4);
+        let error = Schema::parse("[fields.a]\nkind = \"regex\"\npattern = \"(\"\n").unwrap_err();
+        assert!(
+            format!("{:?}", error).contains("unclosed group"),
+            "{:?}",
+            error
+        );
+        assert!(Schema::parse("[fields.a]\nkind = \"date\"\n").is_err());
+        assert_eq!(
+            parse_records("byr:1 iyr\n").unwrap_err().to_string(),
+            r#"day 4, line 1, column 7: expected ":", found "iyr""#
+        );
     }
 }
@@ -1,31 +1,37 @@
 use std::collections::BTreeSet;
 
+use aoc_common::{Answer, Coord, Grid, ParseError, Shape, Solution};
 use eyre::{bail, eyre, Result};
 
-const INPUT: &str = include_str!("../input5.txt");
+pub fn solve() -> eyre::Result<(u32, u32)> {
+    let input = crate::input(5)?;
+    Ok((solve1(&input)?, solve2(&input)?))
+}
+
+pub struct Day5;
 
-pub fn solve() -> eyre::Result<()> {
-    println!("day5.1: {}", solve1(INPUT)?);
-    println!("day5.2: {}", solve2(INPUT)?);
-    Ok(())
+impl Solution for Day5 {
+    fn part1(&self, input: &str) -> eyre::Result<Answer> {
+        Ok(solve1(input)?.into())
+    }
+    fn part2(&self, input: &str) -> eyre::Result<Answer> {
+        Ok(solve2(input)?.into())
+    }
+    fn input_shape(&self) -> Option<Shape> {
+        Some(Shape::Lines("[FB]{7}[LR]{3}"))
+    }
 }
 
 fn solve1(input: &str) -> eyre::Result<u32> {
-    input
-        .lines()
-        .map(seat_num)
-        .collect::<Result<Vec<_>>>()?
+    parse(input)?
         .iter()
         .max()
         .ok_or_else(|| eyre!("No passes provided"))
-        .map(|x| *x)
+        .copied()
 }
 
 fn solve2(input: &str) -> eyre::Result<u32> {
-    let seats = input
-        .lines()
-        .map(seat_num)
-        .collect::<Result<BTreeSet<_>>>()?;
+    let seats = parse(input)?;
     if seats.is_empty() {
         bail!("No passes provided");
     }
@@ -38,36 +44,124 @@ fn solve2(input: &str) -> eyre::Result<u32> {
     bail!("No valid candidate found")
 }
 
-fn seat_num(pass: &str) -> eyre::Result<u32> {
-    let mut row_min = 0;
-    let mut row_max = 127;
-    let mut seat_min = 0;
-    let mut seat_max = 7;
-    for (c, jump) in pass.chars().zip([64, 32, 16, 8, 4, 2, 1, 4, 2, 1].iter()) {
-        match c {
-            'F' => row_max -= jump,
-            'B' => row_min += jump,
-            'R' => seat_min += jump,
-            'L' => seat_max -= jump,
-            _ => bail!("Invalid char {} in line {}", c, pass),
-        }
-    }
-    check_eq(row_min, row_max, "row", pass)?;
-    check_eq(seat_min, seat_max, "seat", pass)?;
-    Ok(row_max * 8 + seat_max)
+/// The seat IDs of the passes in `input`.
+fn parse(input: &str) -> Result<BTreeSet<u32>, ParseError> {
+    input
+        .lines()
+        .map(|pass| Plane::PUZZLE.decode(pass))
+        .collect::<Result<_, _>>()
+        .map_err(|e| e.locate(5, input))
+}
+
+/// A plane of `2^row_bits` rows with `2^column_bits` seats each. A boarding pass picks the
+/// front (`F`) or back (`B`) half of the rows once per row bit, then the left (`L`) or right
+/// (`R`) half of the row once per column bit. Read as binary with `B` and `R` as 1, the pass is
+/// the seat ID `row * columns + column`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Plane {
+    row_bits: u32,
+    column_bits: u32,
 }
 
-fn check_eq(min: u32, max: u32, name: &str, pass: &str) -> eyre::Result<()> {
-    if max != min {
-        bail!(
-            "Unspecified {} in pass {} (seat_min: {}, seat_max: {})",
-            name,
-            pass,
-            min,
-            max
+impl Plane {
+    /// 128 rows of 8 seats.
+    pub const PUZZLE: Plane = Plane::new(7, 3);
+
+    /// Panics if the seat IDs don't fit into a `u32`.
+    pub const fn new(row_bits: u32, column_bits: u32) -> Self {
+        assert!(
+            matches!(row_bits.checked_add(column_bits), Some(bits) if bits < 32),
+            "the plane has too many seats"
         );
+        Plane {
+            row_bits,
+            column_bits,
+        }
+    }
+
+    pub fn row_bits(&self) -> u32 {
+        self.row_bits
+    }
+
+    pub fn column_bits(&self) -> u32 {
+        self.column_bits
+    }
+
+    pub fn rows(&self) -> u32 {
+        1 << self.row_bits
+    }
+
+    pub fn columns(&self) -> u32 {
+        1 << self.column_bits
+    }
+
+    /// The number of seats, one more than the largest seat ID.
+    pub fn seats(&self) -> u32 {
+        self.rows() * self.columns()
+    }
+
+    /// The seat ID of a boarding pass.
+    pub fn decode(&self, pass: &str) -> Result<u32, ParseError> {
+        let length = (self.row_bits + self.column_bits) as usize;
+        let mut seat = 0;
+        for (i, (start, c)) in pass.char_indices().enumerate() {
+            if i == length {
+                return Err(ParseError::new(
+                    &pass[start..],
+                    "expected the end of the pass",
+                ));
+            }
+            let half = &pass[start..start + c.len_utf8()];
+            let bit = match (i < self.row_bits as usize, c) {
+                (true, 'F') | (false, 'L') => 0,
+                (true, 'B') | (false, 'R') => 1,
+                (true, _) => return Err(ParseError::new(half, "expected F or B")),
+                (false, _) => return Err(ParseError::new(half, "expected L or R")),
+            };
+            seat = seat << 1 | bit;
+        }
+        let found = pass.chars().count();
+        if found < length {
+            return Err(ParseError::missing(
+                pass,
+                format!("a pass of {} characters", length),
+            ));
+        }
+        Ok(seat)
+    }
+
+    /// The boarding pass of a seat, `None` if the plane has no such seat.
+    pub fn encode(&self, seat: u32) -> Option<String> {
+        if seat >= self.seats() {
+            return None;
+        }
+        let bits = self.row_bits + self.column_bits;
+        let pass = (0..bits)
+            .rev()
+            .map(|bit| {
+                let back_or_right = seat >> bit & 1 == 1;
+                match (bit >= self.column_bits, back_or_right) {
+                    (true, false) => 'F',
+                    (true, true) => 'B',
+                    (false, false) => 'L',
+                    (false, true) => 'R',
+                }
+            })
+            .collect();
+        Some(pass)
+    }
+
+    /// The seats from front to back, `#` for the `occupied` ones and `.` for free ones. Panics
+    /// on seats the plane doesn't have.
+    pub fn render(&self, occupied: &BTreeSet<u32>) -> String {
+        let columns = self.columns();
+        let mut map = Grid::new(columns as usize, self.rows() as usize, '.');
+        for &seat in occupied {
+            assert!(seat < self.seats(), "the plane has no seat {}", seat);
+            map[Coord::new((seat % columns).into(), (seat / columns).into())] = '#';
+        }
+        map.to_string()
     }
-    Ok(())
 }
 
 #[cfg(test)]
@@ -76,7 +170,8 @@ mod tests {
 
     #[test]
     fn day5() {
-        assert_eq!(solve1(INPUT).unwrap(), 896);
+        let input = crate::input(5).unwrap();
+        assert_eq!(solve1(&input).unwrap(), 896);
     }
 
     #[test]
@@ -86,4 +181,73 @@ mod tests {
         assert_eq!(solve1("FFFBBBFRRR").unwrap(), 119);
         assert_eq!(solve1("BBFFBBFRLL").unwrap(), 820);
     }
+
+    #[test]
+    fn passes() {
+        assert_eq!(Plane::PUZZLE.encode(357).unwrap(), "FBFBBFFRLR");
+        assert_eq!(Plane::PUZZLE.encode(1024), None);
+        let error = |pass| {
+            Plane::PUZZLE
+                .decode(pass)
+                .unwrap_err()
+                .locate(5, pass)
+                .to_string()
+        };
+        assert_eq!(
+            error("FBFBBFFRXR"),
+            r#"day 5, line 1, column 9: expected L or R, found "X""#
+        );
+        assert_eq!(
+            error("FBFLBFFRLR"),
+            r#"day 5, line 1, column 4: expected F or B, found "L""#
+        );
+        assert_eq!(
+            error("FBFBBFFRL"),
+            "day 5, line 1, column 10: expected a pass of 10 characters, found nothing"
+        );
+        assert_eq!(
+            error("FBFBBFFRLRR"),
+            r#"day 5, line 1, column 11: expected the end of the pass, found "R""#
+        );
+        assert_eq!(
+            solve1("FBFBBFFRLR\nFBFBB\n").unwrap_err().to_string(),
+            "day 5, line 2, column 6: expected a pass of 10 characters, found nothing"
+        );
+    }
+
+    #[test]
+    fn round_trips() {
+        for plane in [
+            Plane::new(0, 0),
+            Plane::new(0, 2),
+            Plane::new(3, 0),
+            Plane::new(3, 2),
+            Plane::PUZZLE,
+            Plane::new(9, 4),
+        ] {
+            for seat in 0..plane.seats() {
+                let pass = plane.encode(seat).unwrap();
+                assert_eq!(pass.len() as u32, plane.row_bits() + plane.column_bits());
+                assert_eq!(plane.decode(&pass), Ok(seat), "{:?} {}", plane, pass);
+            }
+            assert_eq!(plane.encode(plane.seats()), None);
+        }
+    }
+
+    #[test]
+    fn seat_map() {
+        let plane = Plane::new(2, 2);
+        let seats: BTreeSet<u32> = ["FFLL", "FFRR", "FBLR", "BBRL"]
+            .iter()
+            .map(|pass| plane.decode(pass).unwrap())
+            .collect();
+        assert_eq!(seats.iter().copied().collect::<Vec<_>>(), [0, 3, 5, 14]);
+        assert_eq!(plane.render(&seats), "#..#\n.#..\n....\n..#.\n");
+    }
+
+    #[test]
+    #[should_panic(expected = "the plane has too many seats")]
+    fn too_many_seats() {
+        Plane::new(u32::MAX, 1);
+    }
 }
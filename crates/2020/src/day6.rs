@@ -1,11 +1,24 @@
 use std::collections::HashMap;
 
-const INPUT: &str = include_str!("../input6.txt");
+use aoc_common::{Answer, Shape, Solution};
 
-pub fn solve() -> eyre::Result<()> {
-    println!("day6.1: {}", solve1(INPUT));
-    println!("day6.2: {}", solve2(INPUT));
-    Ok(())
+pub fn solve() -> eyre::Result<(usize, usize)> {
+    let input = crate::input(6)?;
+    Ok((solve1(&input), solve2(&input)))
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    fn part1(&self, input: &str) -> eyre::Result<Answer> {
+        Ok(solve1(input).into())
+    }
+    fn part2(&self, input: &str) -> eyre::Result<Answer> {
+        Ok(solve2(input).into())
+    }
+    fn input_shape(&self) -> Option<Shape> {
+        Some(Shape::Blocks(&[Shape::Lines("[a-z]+")]))
+    }
 }
 
 fn solve1(input: &str) -> usize {
@@ -47,47 +60,7 @@ mod tests {
 
     #[test]
     fn day6() {
-        assert_eq!(solve1(INPUT), 6585);
-    }
-
-    #[test]
-    fn part1_ex() {
-        let input = "abc
-
-a
-b
-c
-
-ab
-ac
-
-a
-a
-a
-a
-
-b";
-        assert_eq!(solve1(input), 11);
-    }
-
-    #[test]
-    fn part2_ex() {
-        let input = "abc
-
-a
-b
-c
-
-ab
-ac
-
-a
-a
-a
-a
-
-b";
-
-        assert_eq!(solve2(input), 6);
+        let input = crate::input(6).unwrap();
+        assert_eq!(solve1(&input), 6585);
     }
 }
@@ -1,14 +1,28 @@
 use std::collections::{HashMap, HashSet};
 
+use aoc_common::{Answer, Shape, Solution};
 use eyre::{eyre, Context};
 use tracing::debug;
 
-const INPUT: &str = include_str!("../input7.txt");
+pub fn solve() -> eyre::Result<(usize, usize)> {
+    let input = crate::input(7)?;
+    Ok((solve1(&input)?, solve2(&input)?))
+}
+
+pub struct Day7;
 
-pub fn solve() -> eyre::Result<()> {
-    println!("day7.1: {}", solve1(INPUT)?);
-    println!("day7.2: {}", solve2(INPUT)?);
-    Ok(())
+impl Solution for Day7 {
+    fn part1(&self, input: &str) -> eyre::Result<Answer> {
+        Ok(solve1(input)?.into())
+    }
+    fn part2(&self, input: &str) -> eyre::Result<Answer> {
+        Ok(solve2(input)?.into())
+    }
+    fn input_shape(&self) -> Option<Shape> {
+        Some(Shape::Lines(
+            r"\w+ \w+ bags contain (no other bags|\d+ \w+ \w+ bags?(, \d+ \w+ \w+ bags?)*)\.",
+        ))
+    }
 }
 
 fn solve1(input: &str) -> eyre::Result<usize> {
@@ -138,49 +152,8 @@ mod tests {
 
     #[test]
     fn day7() {
-        assert_eq!(solve1(INPUT).unwrap(), 179);
-        assert_eq!(solve2(INPUT).unwrap(), 18925);
-    }
-
-    #[test]
-    fn part1_ex() {
-        let input = "light red bags contain 1 bright white bag, 2 muted yellow bags.
-dark orange bags contain 3 bright white bags, 4 muted yellow bags.
-bright white bags contain 1 shiny gold bag.
-muted yellow bags contain 2 shiny gold bags, 9 faded blue bags.
-shiny gold bags contain 1 dark olive bag, 2 vibrant plum bags.
-dark olive bags contain 3 faded blue bags, 4 dotted black bags.
-vibrant plum bags contain 5 faded blue bags, 6 dotted black bags.
-faded blue bags contain no other bags.
-dotted black bags contain no other bags.";
-        assert_eq!(solve1(input).unwrap(), 4);
-    }
-
-    #[test]
-    fn part2_ex1() {
-        let input = "light red bags contain 1 bright white bag, 2 muted yellow bags.
-dark orange bags contain 3 bright white bags, 4 muted yellow bags.
-bright white bags contain 1 shiny gold bag.
-muted yellow bags contain 2 shiny gold bags, 9 faded blue bags.
-shiny gold bags contain 1 dark olive bag, 2 vibrant plum bags.
-dark olive bags contain 3 faded blue bags, 4 dotted black bags.
-vibrant plum bags contain 5 faded blue bags, 6 dotted black bags.
-faded blue bags contain no other bags.
-dotted black bags contain no other bags.
-";
-        assert_eq!(solve2(input).unwrap(), 32);
-    }
-
-    #[test]
-    fn part2_ex2() {
-        let input = "shiny gold bags contain 2 dark red bags.
-dark red bags contain 2 dark orange bags.
-dark orange bags contain 2 dark yellow bags.
-dark yellow bags contain 2 dark green bags.
-dark green bags contain 2 dark blue bags.
-dark blue bags contain 2 dark violet bags.
-dark violet bags contain no other bags.
-";
-        assert_eq!(solve2(input).unwrap(), 126);
+        let input = crate::input(7).unwrap();
+        assert_eq!(solve1(&input).unwrap(), 179);
+        assert_eq!(solve2(&input).unwrap(), 18925);
     }
 }
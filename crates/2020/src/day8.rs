@@ -1,15 +1,30 @@
 use std::{collections::BTreeSet, str::FromStr};
 
+use aoc_common::{Answer, Shape, Solution};
 use eyre::{bail, eyre, Result};
 
-const INPUT: &str = include_str!("../input8.txt");
+pub fn solve() -> Result<(i32, i32)> {
+    let input = crate::input(8)?;
+    let program = parse(&input)?;
 
-pub(crate) fn solve() -> Result<()> {
-    let program = parse(INPUT)?;
+    Ok((part1(&program), part2(program)))
+}
+
+pub struct Day8;
 
-    println!("day8.1: {}", part1(&program));
-    println!("day8.2: {}", part2(program));
-    Ok(())
+impl Solution for Day8 {
+    fn part1(&self, input: &str) -> Result<Answer> {
+        Ok(part1(&parse(input)?).into())
+    }
+    fn part2(&self, input: &str) -> Result<Answer> {
+        Ok(part2(parse(input)?).into())
+    }
+    fn parse(&self, input: &str) -> Option<Result<()>> {
+        aoc_common::parsed(parse(input))
+    }
+    fn input_shape(&self) -> Option<Shape> {
+        Some(Shape::Lines(r"(acc|jmp|nop) [+-]\d+"))
+    }
 }
 
 fn part1(program: &[OpCode]) -> i32 {
@@ -114,7 +129,7 @@ impl FromStr for OpCode {
     type Err = eyre::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut tokens = s.trim().split_whitespace();
+        let mut tokens = s.split_whitespace();
 
         let code = tokens
             .next()
@@ -138,28 +153,10 @@ mod tests {
 
     #[test]
     fn day8() {
-        let program = parse(INPUT).unwrap();
+        let input = crate::input(8).unwrap();
+        let program = parse(&input).unwrap();
 
         assert_eq!(part1(&program), 1654);
         assert_eq!(part2(program), 833);
     }
-
-    #[test]
-    fn day8_ex() {
-        let program = parse(
-            "nop +0
-acc +1
-jmp +4
-acc +3
-jmp -3
-acc -99
-acc +1
-jmp -4
-acc +6
-jmp +1",
-        )
-        .unwrap();
-
-        assert_eq!(part2(program), 8);
-    }
 }
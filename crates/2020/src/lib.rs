@@ -0,0 +1,33 @@
+pub mod day1;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+
+use aoc_common::{InputError, Puzzle};
+
+pub const PUZZLES: &[Puzzle] = &[
+    Puzzle::new(2020, 1, &day1::Day1),
+    Puzzle::new(2020, 2, &day2::Day2),
+    Puzzle::new(2020, 3, &day3::Day3),
+    Puzzle::new(2020, 4, &day4::Day4),
+    Puzzle::new(2020, 5, &day5::Day5),
+    Puzzle::new(2020, 6, &day6::Day6),
+    Puzzle::new(2020, 7, &day7::Day7),
+    Puzzle::new(2020, 8, &day8::Day8),
+];
+
+pub fn input(day: u32) -> Result<String, InputError> {
+    aoc_common::read_input(2020, day)
+}
+
+#[cfg(test)]
+pub mod tests {
+
+    pub fn init_logger() {
+        let _ = tracing_subscriber::fmt::try_init();
+    }
+}
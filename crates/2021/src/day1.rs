@@ -1,17 +1,39 @@
+use aoc_common::{Answer, Shape, Solution};
 use eyre::Result;
 
 use crate::input;
 
 pub fn solve() -> Result<(usize, usize)> {
-    let input = input(1);
-    let depths: Vec<_> = input
-        .lines()
-        .map(|line| line.parse::<u32>())
-        .collect::<Result<_, _>>()?;
+    let input = input(1)?;
+    let depths = parse(&input)?;
 
     Ok((part1(&depths), part2(&depths)))
 }
 
+pub struct Day1;
+
+impl Solution for Day1 {
+    fn part1(&self, input: &str) -> Result<Answer> {
+        Ok(part1(&parse(input)?).into())
+    }
+    fn part2(&self, input: &str) -> Result<Answer> {
+        Ok(part2(&parse(input)?).into())
+    }
+    fn parse(&self, input: &str) -> Option<Result<()>> {
+        aoc_common::parsed(parse(input))
+    }
+    fn input_shape(&self) -> Option<Shape> {
+        Some(Shape::Lines(r"\d+"))
+    }
+}
+
+fn parse(input: &str) -> Result<Vec<u32>> {
+    Ok(input
+        .lines()
+        .map(|line| line.parse::<u32>())
+        .collect::<Result<_, _>>()?)
+}
+
 fn part2(depths: &[u32]) -> usize {
     let windows = depths
         .iter()
@@ -38,4 +60,4 @@ mod tests {
     fn day1() {
         assert_eq!(solve().unwrap(), (1681, 1704))
     }
-}
\ No newline at end of file
+}
@@ -1,18 +1,39 @@
 use std::collections::VecDeque;
 
+use aoc_common::{Answer, Shape, Solution};
 use eyre::Result;
 use tracing::debug;
 
 use crate::input;
 
 pub fn solve() -> Result<(u32, u64)> {
-    let input = input(10);
-    let lines = parse(&input);
-    let analyses = lines.into_iter().map(Line::analyze).collect::<Vec<_>>();
+    let input = input(10)?;
+    let analyses = analyze(&input);
 
     Ok((part1(&analyses), part2(&analyses)))
 }
 
+pub struct Day10;
+
+impl Solution for Day10 {
+    fn part1(&self, input: &str) -> Result<Answer> {
+        Ok(part1(&analyze(input)).into())
+    }
+    fn part2(&self, input: &str) -> Result<Answer> {
+        Ok(part2(&analyze(input)).into())
+    }
+    fn parse(&self, input: &str) -> Option<Result<()>> {
+        aoc_common::parsed(eyre::Ok(parse(input)))
+    }
+    fn input_shape(&self) -> Option<Shape> {
+        Some(Shape::Lines(r"[()\[\]{}<>]+"))
+    }
+}
+
+fn analyze(input: &str) -> Vec<LineAnalysis> {
+    parse(input).into_iter().map(Line::analyze).collect()
+}
+
 fn part1(analyses: &[LineAnalysis]) -> u32 {
     analyses
         .iter()
@@ -26,7 +47,7 @@ fn part2(analyses: &[LineAnalysis]) -> u64 {
         .filter_map(LineAnalysis::score_incomplete)
         .collect::<Vec<_>>();
     scores.sort_unstable();
-    let result = scores[(scores.len() / 2)];
+    let result = scores[scores.len() / 2];
     debug!("result: {} in len {}: {:?}", result, scores.len(), scores);
     result
 }
@@ -175,26 +196,6 @@ mod tests {
         assert_eq!(part2, 2802519786);
     }
 
-    #[test]
-    fn day10_ex() {
-        let input = "[({(<(())[]>[[{[]{<()<>>
-[(()[<>])]({[<{<<[]>>(
-{([(<{}[<>[]}>{[]{[(<()>
-(((({<>}<{<{<>}{[]{[]{}
-[[<[([]))<([[{}[[()]]]
-[{[{({}]{}}([{[{{{}}([]
-{<[[]]>}<{[{[{[]{()[[[]
-[<(<(<(<{}))><([]([]()
-<{([([[(<>()){}]>(<<{{
-<{([{{}}[<[[[<>{}]]]>[]]";
-        let lines = parse(input);
-        let analyses = lines.into_iter().map(Line::analyze).collect::<Vec<_>>();
-        let part1 = part1(&analyses);
-        let part2 = part2(&analyses);
-        assert_eq!(part1, 26397);
-        assert_eq!(part2, 288957);
-    }
-
     #[test]
     fn incomplete_score() {
         let incomplete = LineAnalysis::Incomplete {
@@ -1,10 +1,39 @@
 use std::collections::BTreeSet;
 
+use aoc_common::{Answer, Coord, Grid, Shape, Solution};
 use eyre::Result;
 use tracing::debug;
 
 pub fn solve() -> Result<(usize, u64)> {
-    let input = "4871252763
+    let mut octopuses = parse(INPUT)?;
+
+    Ok(parts(&mut octopuses))
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    fn part1(&self, input: &str) -> Result<Answer> {
+        Ok(parts(&mut parse(input)?).0.into())
+    }
+    fn part2(&self, input: &str) -> Result<Answer> {
+        Ok(parts(&mut parse(input)?).1.into())
+    }
+    fn embedded_input(&self) -> Option<&'static str> {
+        Some(INPUT)
+    }
+    fn parse(&self, input: &str) -> Option<Result<()>> {
+        aoc_common::parsed(parse(input))
+    }
+    fn input_shape(&self) -> Option<Shape> {
+        Some(Shape::Grid {
+            cell: "[0-9]",
+            size: Some((10, 10)),
+        })
+    }
+}
+
+const INPUT: &str = "4871252763
 8533428173
 7182186813
 2128441541
@@ -15,13 +44,9 @@ pub fn solve() -> Result<(usize, u64)> {
 2651347271
 7788154252
 ";
-    let mut octopuses = parse(input);
-
-    Ok(parts(&mut octopuses))
-}
 
 fn parts(octopuses: &mut Octopuses) -> (usize, u64) {
-    let octopus_count = octopuses.0.iter().map(|l| l.len()).sum();
+    let octopus_count = octopuses.0.width() * octopuses.0.height();
     let mut part1 = 0;
     let mut part2 = None;
     for i in 1.. {
@@ -39,91 +64,60 @@ fn parts(octopuses: &mut Octopuses) -> (usize, u64) {
     (part1, part2.unwrap())
 }
 
-fn parse(input: &str) -> Octopuses {
-    let octopuses = input
-        .lines()
-        .map(|line| line.chars().map(|c| c as u8 - b'0').collect())
-        .collect();
-    Octopuses(octopuses)
+fn parse(input: &str) -> Result<Octopuses> {
+    Ok(Octopuses(Grid::parse_digits(input)?))
 }
 
 #[derive(PartialEq, Eq)]
-struct Octopuses(Vec<Vec<u8>>);
+struct Octopuses(Grid<u8>);
 
 impl std::fmt::Debug for Octopuses {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         writeln!(f, "Octopuses:")?;
-        for line in &self.0 {
-            for c in line {
-                write!(f, "{}", c)?;
-            }
-            writeln!(f)?;
-        }
-        Ok(())
+        write!(f, "{}", self.0)
     }
 }
 
 impl Octopuses {
-    fn step(&mut self) -> BTreeSet<(usize, usize)> {
-        for row in &mut self.0 {
-            for octopus in row {
-                *octopus += 1;
-            }
+    fn step(&mut self) -> BTreeSet<Coord> {
+        for (_, octopus) in self.0.iter_mut() {
+            *octopus += 1;
         }
 
         let mut flashed = BTreeSet::new();
-        for x in 0..self.0.len() {
-            for y in 0..self.0[x].len() {
-                if self.at(x, y).copied().unwrap() > 9 {
-                    self.flash(x, y, &mut flashed);
-                }
+        for coord in self.0.coords() {
+            if self.0[coord] > 9 {
+                self.flash(coord, &mut flashed);
             }
         }
 
-        for (x, y) in &flashed {
-            if let Some(flashed_octopus) = self.at(*x, *y) {
-                assert!(
-                    *flashed_octopus > 9,
-                    "Flashed octopus with value {}",
-                    flashed_octopus
-                );
-                *flashed_octopus = 0;
-            } else {
-                panic!("Invalid flashed octopus at {},{}", x, y);
-            }
+        for &coord in &flashed {
+            let flashed_octopus = &mut self.0[coord];
+            assert!(
+                *flashed_octopus > 9,
+                "Flashed octopus with value {}",
+                flashed_octopus
+            );
+            *flashed_octopus = 0;
         }
 
         flashed
     }
 
-    fn flash(&mut self, x: usize, y: usize, flashed: &mut BTreeSet<(usize, usize)>) {
-        if !flashed.insert((x, y)) {
+    fn flash(&mut self, coord: Coord, flashed: &mut BTreeSet<Coord>) {
+        if !flashed.insert(coord) {
             return;
         }
-        debug!("Flashing {},{}", x, y);
-        for (x1, y1) in neighbors(x, y) {
-            if let Some(neighbor) = self.at(x1, y1) {
-                debug!("Flashed neighbor of {},{}:  {},{}", x, y, x1, y1);
-                *neighbor += 1;
-                if *neighbor > 9 {
-                    self.flash(x1, y1, flashed);
-                }
+        debug!("Flashing {:?}", coord);
+        let neighbors: Vec<Coord> = self.0.neighbors8(coord).collect();
+        for neighbor in neighbors {
+            debug!("Flashed neighbor of {:?}:  {:?}", coord, neighbor);
+            self.0[neighbor] += 1;
+            if self.0[neighbor] > 9 {
+                self.flash(neighbor, flashed);
             }
         }
     }
-
-    fn at(&mut self, x: usize, y: usize) -> Option<&mut u8> {
-        self.0.get_mut(x)?.get_mut(y)
-    }
-}
-
-fn neighbors(x: usize, y: usize) -> Vec<(usize, usize)> {
-    let start_x = if x == 0 { x } else { x - 1 };
-    let start_y = if y == 0 { y } else { y - 1 };
-    (start_x..=x + 1)
-        .flat_map(|x1| (start_y..=y + 1).map(move |y1| (x1, y1)))
-        .filter(|(x1, y1)| *x1 != x || *y1 != y)
-        .collect()
 }
 
 #[cfg(test)]
@@ -150,7 +144,7 @@ mod tests {
 6882881134
 4846848554
 5283751526";
-        let mut octopuses = parse(input);
+        let mut octopuses = parse(input).unwrap();
         assert_eq!(octopuses.step().len(), 0);
 
         assert_eq!(
@@ -167,6 +161,7 @@ mod tests {
 5957959665
 6394862637"
             )
+            .unwrap()
         );
 
         eprintln!("{:?}", octopuses);
@@ -187,6 +182,7 @@ mod tests {
 9000000876
 8700006848"
             )
+            .unwrap()
         );
     }
 
@@ -198,7 +194,8 @@ mod tests {
 19191
 19991
 11111",
-        );
+        )
+        .unwrap();
         assert_eq!(octopuses.step().len(), 9);
 
         assert_eq!(
@@ -210,25 +207,17 @@ mod tests {
 40004
 34543"
             )
+            .unwrap()
         );
     }
 
     #[test]
     fn test_neighbors() {
+        let octopuses = parse("111\n111\n111").unwrap();
+        assert_eq!(octopuses.0.neighbors8(Coord::new(1, 1)).count(), 8);
         assert_eq!(
-            neighbors(5, 10),
-            vec![
-                (4, 9),
-                (4, 10),
-                (4, 11),
-                (5, 9),
-                (5, 11),
-                (6, 9),
-                (6, 10),
-                (6, 11)
-            ]
+            octopuses.0.neighbors8(Coord::new(0, 0)).collect::<Vec<_>>(),
+            vec![Coord::new(1, 0), Coord::new(1, 1), Coord::new(0, 1)]
         );
-
-        assert!(dbg!(neighbors(0, 2)).contains(&(1, 1)));
     }
 }
@@ -1,11 +1,12 @@
 use std::collections::{BTreeMap, BTreeSet};
 
+use aoc_common::{Answer, Shape, Solution};
 use eyre::{eyre, Result};
 
 use crate::input;
 
 pub fn solve() -> Result<(usize, usize)> {
-    let input = input(12);
+    let input = input(12)?;
 
     let map = parse(&input)?;
 
@@ -15,6 +16,25 @@ pub fn solve() -> Result<(usize, usize)> {
     ))
 }
 
+pub struct Day12;
+
+impl Solution for Day12 {
+    fn part1(&self, input: &str) -> Result<Answer> {
+        let map = parse(input)?;
+        Ok(map.paths(Default::default(), START, Some(START)).into())
+    }
+    fn part2(&self, input: &str) -> Result<Answer> {
+        let map = parse(input)?;
+        Ok(map.paths(Default::default(), START, None).into())
+    }
+    fn parse(&self, input: &str) -> Option<Result<()>> {
+        aoc_common::parsed(parse(input))
+    }
+    fn input_shape(&self) -> Option<Shape> {
+        Some(Shape::Lines("[a-zA-Z]+-[a-zA-Z]+"))
+    }
+}
+
 impl CaveMap {
     fn paths(&self, mut path: Vec<i32>, current_cave: i32, visited_twice: Option<i32>) -> usize {
         if current_cave == END {
@@ -44,22 +64,6 @@ struct CaveMap {
     connections: BTreeMap<i32, BTreeSet<i32>>,
 }
 
-#[derive(PartialEq, Eq, PartialOrd, Ord, Clone)]
-struct Path {
-    visited_caves: Vec<i32>,
-    current_cave: i32,
-    visited_small_cave_twice: bool,
-}
-
-impl std::fmt::Debug for Path {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for cave in &self.visited_caves {
-            write!(f, "{},", cave)?;
-        }
-        write!(f, "{}", self.current_cave)
-    }
-}
-
 const START: i32 = 0;
 const END: i32 = 1;
 
@@ -1,20 +1,43 @@
 use std::{collections::BTreeSet, iter};
 
+use aoc_common::{ocr, Answer, Shape, Solution};
 use eyre::Result;
 use tracing::debug;
 
 use crate::input;
 
 pub fn solve() -> Result<(usize, String)> {
-    let input = input(13);
+    let input = input(13)?;
     let (dots, instructions) = parse(&input)?;
 
     Ok((
         run(dots.clone(), &instructions[..1]).len(),
-        dbg_dots(&run(dots, &instructions)),
+        ocr::read_letters(&dbg_dots(&run(dots, &instructions)))?,
     ))
 }
 
+pub struct Day13;
+
+impl Solution for Day13 {
+    fn part1(&self, input: &str) -> Result<Answer> {
+        let (dots, instructions) = parse(input)?;
+        Ok(run(dots, &instructions[..1]).len().into())
+    }
+    fn part2(&self, input: &str) -> Result<Answer> {
+        let (dots, instructions) = parse(input)?;
+        Ok(ocr::read_letters(&dbg_dots(&run(dots, &instructions)))?.into())
+    }
+    fn parse(&self, input: &str) -> Option<Result<()>> {
+        aoc_common::parsed(parse(input))
+    }
+    fn input_shape(&self) -> Option<Shape> {
+        Some(Shape::Blocks(&[
+            Shape::Lines(r"\d+,\d+"),
+            Shape::Lines(r"fold along [xy]=\d+"),
+        ]))
+    }
+}
+
 fn run(mut dots: BTreeSet<Coord>, instructions: &[FoldInstruction]) -> BTreeSet<Coord> {
     debug!("Initial dots:\n{}", dbg_dots(&dots));
     for (direction, pos) in instructions {
@@ -42,8 +65,8 @@ fn run(mut dots: BTreeSet<Coord>, instructions: &[FoldInstruction]) -> BTreeSet<
 fn dbg_dots(dots: &BTreeSet<Coord>) -> String {
     let xmax = *dots.iter().map(|(x, _)| x).max().unwrap();
     let ymax = *dots.iter().map(|(_, y)| y).max().unwrap();
-    let line = iter::repeat('.').take(xmax + 1).collect::<Vec<_>>();
-    let mut lines = iter::repeat(line).take(ymax + 1).collect::<Vec<_>>();
+    let line = iter::repeat_n('.', xmax + 1).collect::<Vec<_>>();
+    let mut lines = iter::repeat_n(line, ymax + 1).collect::<Vec<_>>();
     for (x, y) in dots {
         lines[*y][*x] = '#';
     }
@@ -109,47 +132,6 @@ mod tests {
         let (part1, part2) = solve().unwrap();
 
         assert_eq!(part1, 818);
-        assert_eq!(
-            part2,
-            "\
-#....###...##..###..###..####..##..###.
-#....#..#.#..#.#..#.#..#.#....#..#.#..#
-#....#..#.#....#..#.#..#.###..#....###.
-#....###..#.##.###..###..#....#....#..#
-#....#.#..#..#.#....#.#..#....#..#.#..#
-####.#..#..###.#....#..#.####..##..###.
-"
-        );
-    }
-
-    #[test]
-    fn day13_ex() {
-        let (dots, instructions) = parse(
-            "6,10
-0,14
-9,10
-0,3
-10,4
-4,11
-6,0
-6,12
-4,1
-0,13
-10,12
-3,4
-3,0
-8,4
-1,10
-2,14
-8,10
-9,0
-
-fold along y=7
-fold along x=5",
-        )
-        .unwrap();
-        let part1 = run(dots, &instructions[..1]).len();
-
-        assert_eq!(part1, 17);
+        assert_eq!(part2, "LRGPRECB");
     }
 }
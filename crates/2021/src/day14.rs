@@ -1,17 +1,40 @@
 use std::collections::BTreeMap;
 
+use aoc_common::{Answer, Shape, Solution};
 use eyre::Result;
 
 use crate::input;
 
 pub fn solve() -> Result<(u64, u64)> {
-    let input = input(14);
+    let input = input(14)?;
 
     let (template, rules) = parse(&input);
 
     Ok((run(template.clone(), &rules, 10), run(template, &rules, 40)))
 }
 
+pub struct Day14;
+
+impl Solution for Day14 {
+    fn part1(&self, input: &str) -> Result<Answer> {
+        let (template, rules) = parse(input);
+        Ok(run(template, &rules, 10).into())
+    }
+    fn part2(&self, input: &str) -> Result<Answer> {
+        let (template, rules) = parse(input);
+        Ok(run(template, &rules, 40).into())
+    }
+    fn parse(&self, input: &str) -> Option<Result<()>> {
+        aoc_common::parsed(eyre::Ok(parse(input)))
+    }
+    fn input_shape(&self) -> Option<Shape> {
+        Some(Shape::Blocks(&[
+            Shape::Line("[A-Z]+"),
+            Shape::Lines("[A-Z]{2} -> [A-Z]"),
+        ]))
+    }
+}
+
 fn run(polymer: Vec<u8>, rules: &Rules, iterations: u8) -> u64 {
     let last_char = *polymer.last().unwrap();
     let mut buckets = buckets(polymer);
@@ -82,29 +105,4 @@ mod tests {
         assert_eq!(part1, 3143);
         assert_eq!(part2, 4110215602456);
     }
-
-    #[test]
-    fn day14_ex() {
-        let (template, rules) = parse(
-            "NNCB
-
-CH -> B
-HH -> N
-CB -> H
-NH -> C
-HB -> C
-HC -> B
-HN -> C
-NN -> C
-BH -> H
-NC -> B
-NB -> B
-BN -> B
-BB -> N
-BC -> B
-CC -> N
-CN -> C",
-        );
-        assert_eq!(run(template, &rules, 10), 1588);
-    }
 }
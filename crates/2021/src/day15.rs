@@ -1,144 +1,69 @@
-use std::collections::BTreeMap;
-
-use eyre::Result;
-use fnv::FnvHashSet;
+use aoc_common::{path, Answer, Coord, Grid, Shape, Solution};
+use eyre::{eyre, Result};
 use tracing::debug;
 
 use crate::input;
 
 pub fn solve() -> Result<(u32, u32)> {
-    let input = input(15);
-    let grid1 = parse1(&input);
-    let grid2 = parse2(&input);
+    let input = input(15)?;
+    let cave1 = parse1(&input)?;
+    let cave2 = parse2(&input)?;
 
-    Ok((grid1.solve(), grid2.solve()))
+    Ok((cave1.solve()?, cave2.solve()?))
 }
 
-struct Grid {
-    inner: Vec<Vec<u8>>,
-    start: Coord,
-    target: Coord,
-}
+pub struct Day15;
 
-struct Search {
-    visited: FnvHashSet<Coord>,
-    queue: Queue,
-    target: Coord,
-}
-
-#[derive(Default)]
-struct Queue(BTreeMap<u32, Vec<(Coord, u32)>>);
-impl Queue {
-    fn enqueue(&mut self, coord: Coord, cost: u32, weight: u32) {
-        self.0.entry(cost + weight).or_default().push((coord, cost));
+impl Solution for Day15 {
+    fn part1(&self, input: &str) -> Result<Answer> {
+        Ok(parse1(input)?.solve()?.into())
     }
-    fn pop(&mut self) -> Option<(Coord, u32)> {
-        let best_cost = *self.0.keys().next()?;
-        let best_coords = self.0.get_mut(&best_cost)?;
-        let best_coord = best_coords.pop()?;
-        if best_coords.is_empty() {
-            self.0.remove(&best_cost);
-        }
-        debug!("Popped {:?} from {:?}", best_coord, self.0);
-        Some(best_coord)
+    fn part2(&self, input: &str) -> Result<Answer> {
+        Ok(parse2(input)?.solve()?.into())
     }
-}
-
-impl Search {
-    fn expand(&mut self, grid: &Grid) -> Option<u32> {
-        let (candidate_coord, candidate_cost) = self.queue.pop().unwrap();
-
-        let reached_target = candidate_coord == self.target;
-        if reached_target {
-            return Some(candidate_cost);
-        }
-        self.visited.insert(candidate_coord);
-        debug!("Exploring {:?} next", candidate_coord);
-        for (coord, cost) in grid.neighbors(candidate_coord) {
-            if self.visited.contains(&coord) {
-                continue;
-            }
-            if coord == self.target {
-                return Some(cost as u32 + candidate_cost);
-            }
-            self.queue.enqueue(
-                coord,
-                cost as u32 + candidate_cost,
-                manhattan_distance(coord, self.target),
-            );
-        }
-        None
+    fn input_shape(&self) -> Option<Shape> {
+        Some(Shape::Grid {
+            cell: "[0-9]",
+            size: None,
+        })
     }
 }
 
-fn manhattan_distance(coord: (usize, usize), target: (usize, usize)) -> u32 {
-    ((target.0 - coord.0) + (target.1 - coord.1)) as u32
+struct Cave {
+    risk: Grid<u8>,
+    start: Coord,
+    target: Coord,
 }
 
-impl Grid {
-    fn search(&self) -> Search {
-        let mut queue = Queue::default();
-        for (coord, cost) in self.neighbors(self.start) {
-            queue.enqueue(coord, cost as u32, manhattan_distance(coord, self.target));
+impl Cave {
+    fn new(risk: Grid<u8>) -> Self {
+        let target = Coord::new(risk.width() as i64 - 1, risk.height() as i64 - 1);
+        Cave {
+            risk,
+            start: Coord::new(0, 0),
+            target,
         }
-        Search {
-            visited: FnvHashSet::from_iter([(self.start)]),
-            queue,
-            target: self.target,
-        }
-    }
-    fn solve(&self) -> u32 {
-        let mut search = self.search();
-        loop {
-            if let Some(result) = search.expand(self) {
-                return result;
-            }
-        }
-    }
-    fn neighbors(&self, coord: Coord) -> Vec<(Coord, u8)> {
-        neighbors(coord)
-            .into_iter()
-            .filter_map(|coord| Some((coord, self.at(coord)?)))
-            .collect()
     }
-    fn at(&self, (x, y): Coord) -> Option<u8> {
-        self.inner.get(x)?.get(y).copied()
+    fn solve(&self) -> Result<u32> {
+        let path = path::astar(
+            [self.start],
+            |&coord| self.risk.neighbors4(coord),
+            |_, &to| self.risk[to] as u64,
+            |coord| coord.manhattan(self.target),
+            |&coord| coord == self.target,
+        )
+        .ok_or_else(|| eyre!("the target at {:?} is unreachable", self.target))?;
+        debug!(?path, "lowest risk path");
+        Ok(path.cost as u32)
     }
 }
 
-fn neighbors((x, y): Coord) -> Vec<Coord> {
-    [
-        (x.saturating_sub(1), y),
-        (x + 1, y),
-        (x, y.saturating_sub(1)),
-        (x, y + 1),
-    ]
-    .into_iter()
-    .filter(|c| *c != (x, y))
-    .collect()
-}
-
-type Coord = (usize, usize);
-
-fn parse1(input: &str) -> Grid {
-    let grid = input
-        .lines()
-        .map(|l| l.chars().map(|c| c as u8 - b'0').collect::<Vec<_>>())
-        .collect::<Vec<_>>();
-    let target = (grid.len() - 1, grid[0].len() - 1);
-    Grid {
-        inner: grid,
-        start: (0, 0),
-        target,
-    }
+fn parse1(input: &str) -> Result<Cave> {
+    Ok(Cave::new(Grid::parse_digits(input)?))
 }
 
-fn parse2(input: &str) -> Grid {
-    let original_grid = input
-        .lines()
-        .map(|l| l.chars().map(|c| c as u8 - b'0').collect::<Vec<_>>())
-        .collect::<Vec<_>>();
-    let mut grid = original_grid.clone();
+fn parse2(input: &str) -> Result<Cave> {
+    let tile = Grid::parse_digits(input)?;
 
     fn add_mod(mut x: u8, y: u8) -> u8 {
         for _ in 0..y {
@@ -150,36 +75,13 @@ fn parse2(input: &str) -> Grid {
         x
     }
 
-    let mut extend_right = |i: u8| {
-        for x in 0..grid.len() {
-            grid.get_mut(x)
-                .unwrap()
-                .extend(original_grid.get(x).unwrap().iter().map(|x| add_mod(*x, i)));
-        }
-    };
-
-    for i in 1..5 {
-        extend_right(i);
-    }
-
-    let first_line = grid.clone();
-    let mut extend_down = |i: u8| {
-        grid.extend(
-            first_line
-                .iter()
-                .map(|l| l.iter().map(|x| add_mod(*x, i)).collect()),
-        );
-    };
-
-    for i in 1..5 {
-        extend_down(i);
-    }
-    let target = (grid.len() - 1, grid[0].len() - 1);
-    Grid {
-        inner: grid,
-        start: (0, 0),
-        target,
+    let (width, height) = (tile.width() as i64, tile.height() as i64);
+    let mut risk = Grid::new(tile.width() * 5, tile.height() * 5, 0);
+    for coord in risk.coords() {
+        let original = tile[Coord::new(coord.x % width, coord.y % height)];
+        risk[coord] = add_mod(original, (coord.x / width + coord.y / height) as u8);
     }
+    Ok(Cave::new(risk))
 }
 
 #[cfg(test)]
@@ -194,24 +96,7 @@ mod tests {
     }
 
     #[test]
-    fn day15_ex() {
-        let example_input = "1163751742
-1381373672
-2136511328
-3694931569
-7463417111
-1319128137
-1359912421
-3125421639
-1293138521
-2311944581";
-        let grid = parse1(example_input);
-        assert_eq!(grid.solve(), 40);
-
-        let grid2 = parse2(example_input);
-        for line in grid2.inner {
-            let s = line.iter().map(|x| (*x + b'0') as char).collect::<String>();
-            println!("{}", s);
-        }
+    fn unreachable() {
+        assert!(parse1("").unwrap().solve().is_err());
     }
 }
@@ -2,17 +2,35 @@
 use core::num;
 use std::collections::VecDeque;
 
+use aoc_common::{Answer, Shape, Solution};
 use eyre::Result;
 
 use crate::input;
 
 pub fn solve() -> Result<(u64, i64)> {
-    let input = input(16);
+    let input = input(16)?;
     let packet = parse(&input.trim());
 
     Ok((packet.sum_versions(), packet.evaluate()))
 }
 
+pub struct Day16;
+
+impl Solution for Day16 {
+    fn part1(&self, input: &str) -> Result<Answer> {
+        Ok(part1(input).into())
+    }
+    fn part2(&self, input: &str) -> Result<Answer> {
+        Ok(parse(input.trim()).evaluate().into())
+    }
+    fn parse(&self, input: &str) -> Option<Result<()>> {
+        aoc_common::parsed(eyre::Ok(parse(input)))
+    }
+    fn input_shape(&self) -> Option<Shape> {
+        Some(Shape::Line("[0-9A-F]+"))
+    }
+}
+
 fn part1(input: &str) -> u64 {
     let packet = parse(input.trim());
     packet.sum_versions()
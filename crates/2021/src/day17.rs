@@ -1,14 +1,57 @@
 use std::{iter, ops::RangeInclusive};
 
-use eyre::Result;
+use aoc_common::{Answer, Shape, Solution};
+use eyre::{eyre, Result};
 
 pub fn solve() -> Result<(i32, usize)> {
-    let target_x = 211..=232;
-    let target_y = -124..=-69;
+    let (target_x, target_y) = parse(INPUT)?;
 
     Ok((part1(target_y.clone()), part2(target_x, target_y)))
 }
 
+pub struct Day17;
+
+impl Solution for Day17 {
+    fn part1(&self, input: &str) -> Result<Answer> {
+        let (_, target_y) = parse(input)?;
+        Ok(part1(target_y).into())
+    }
+    fn part2(&self, input: &str) -> Result<Answer> {
+        let (target_x, target_y) = parse(input)?;
+        Ok(part2(target_x, target_y).into())
+    }
+    fn embedded_input(&self) -> Option<&'static str> {
+        Some(INPUT)
+    }
+    fn parse(&self, input: &str) -> Option<Result<()>> {
+        aoc_common::parsed(parse(input))
+    }
+    fn input_shape(&self) -> Option<Shape> {
+        Some(Shape::Line(
+            r"target area: x=-?\d+\.\.-?\d+, y=-?\d+\.\.-?\d+",
+        ))
+    }
+}
+
+const INPUT: &str = "target area: x=211..232, y=-124..-69";
+
+fn parse(input: &str) -> Result<(RangeInclusive<i32>, RangeInclusive<i32>)> {
+    let parse_range = |range: &str, prefix: &str| -> Result<RangeInclusive<i32>> {
+        let (from, to) = range
+            .trim()
+            .strip_prefix(prefix)
+            .and_then(|r| r.split_once(".."))
+            .ok_or_else(|| eyre!("Invalid range {:?}", range))?;
+        Ok(from.parse()?..=to.parse()?)
+    };
+    let (x, y) = input
+        .trim()
+        .strip_prefix("target area: ")
+        .and_then(|ranges| ranges.split_once(','))
+        .ok_or_else(|| eyre!("Invalid target area {:?}", input))?;
+    Ok((parse_range(x, "x=")?, parse_range(y, "y=")?))
+}
+
 fn part1(target_y: RangeInclusive<i32>) -> i32 {
     let y = (max_abs(&target_y)) - 1;
     y_trajectory(y, target_y).max().unwrap()
@@ -63,6 +106,10 @@ mod tests {
 
     #[test]
     fn example() {
+        assert_eq!(
+            parse("target area: x=20..30, y=-10..-5").unwrap(),
+            (20..=30, -10..=-5)
+        );
         assert_eq!(part1(-10..=-5), 45);
         assert_eq!(part2(20..=30, -10..=-5), 112);
     }
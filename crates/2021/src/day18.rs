@@ -1,3 +1,4 @@
+use aoc_common::{Answer, Shape, Solution};
 use eyre::Result;
 use itertools::Itertools;
 use tracing::debug;
@@ -5,12 +6,29 @@ use tracing::debug;
 use crate::input;
 
 pub fn solve() -> Result<(i32, i32)> {
-    let input = input(18);
+    let input = input(18)?;
     let numbers = parse(&input);
 
     Ok((part1(numbers.clone()), part2(numbers)))
 }
 
+pub struct Day18;
+
+impl Solution for Day18 {
+    fn part1(&self, input: &str) -> Result<Answer> {
+        Ok(part1(parse(input)).into())
+    }
+    fn part2(&self, input: &str) -> Result<Answer> {
+        Ok(part2(parse(input)).into())
+    }
+    fn parse(&self, input: &str) -> Option<Result<()>> {
+        aoc_common::parsed(eyre::Ok(parse(input)))
+    }
+    fn input_shape(&self) -> Option<Shape> {
+        Some(Shape::Lines(r"[\[\],0-9]+"))
+    }
+}
+
 fn part1(numbers: Vec<Number>) -> i32 {
     let sum = sum(numbers);
     let tree = NumberTree::from(&sum);
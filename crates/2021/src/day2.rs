@@ -1,19 +1,38 @@
 use std::str::FromStr;
 
+use aoc_common::{Answer, Shape, Solution};
 use eyre::{bail, Result};
 
 use crate::input;
 
 pub fn solve() -> Result<(u32, u32)> {
-    let input = input(2);
-    let directions = input
-        .lines()
-        .map(Dir::from_str)
-        .collect::<Result<Vec<_>>>()?;
+    let input = input(2)?;
+    let directions = parse(&input)?;
 
     Ok((part1(&directions), part2(&directions)))
 }
 
+pub struct Day2;
+
+impl Solution for Day2 {
+    fn part1(&self, input: &str) -> Result<Answer> {
+        Ok(part1(&parse(input)?).into())
+    }
+    fn part2(&self, input: &str) -> Result<Answer> {
+        Ok(part2(&parse(input)?).into())
+    }
+    fn parse(&self, input: &str) -> Option<Result<()>> {
+        aoc_common::parsed(parse(input))
+    }
+    fn input_shape(&self) -> Option<Shape> {
+        Some(Shape::Lines(r"(forward|down|up) \d+"))
+    }
+}
+
+fn parse(input: &str) -> Result<Vec<Dir>> {
+    input.lines().map(Dir::from_str).collect()
+}
+
 fn part1(directions: &[Dir]) -> u32 {
     let mut pos = 0;
     let mut depth = 0;
@@ -1,5 +1,6 @@
 use std::iter;
 
+use aoc_common::{Answer, Shape, Solution};
 use eyre::Result;
 use itertools::Itertools;
 use rayon::iter::{ParallelBridge, ParallelIterator};
@@ -8,6 +9,23 @@ pub fn solve() -> Result<(String, String)> {
     Ok((part1(), part2()))
 }
 
+pub struct Day24;
+
+impl Solution for Day24 {
+    fn part1(&self, _input: &str) -> Result<Answer> {
+        Ok(part1().into())
+    }
+    fn part2(&self, _input: &str) -> Result<Answer> {
+        Ok(part2().into())
+    }
+    fn embedded_input(&self) -> Option<&'static str> {
+        Some(INPUT)
+    }
+    fn input_shape(&self) -> Option<Shape> {
+        Some(Shape::Cycle(&ALU_BLOCK))
+    }
+}
+
 #[allow(clippy::too_many_arguments, clippy::let_and_return)]
 fn optimized_program(
     input1: i64,
@@ -89,19 +107,15 @@ fn optimized_program(
 
 fn part1() -> String {
     first_matching_serial_number(
-        iter::repeat((1..=9).rev())
-            // 6 args
-            .take(6)
-            .multi_cartesian_product(),
+        // 6 args
+        iter::repeat_n((1..=9).rev(), 6).multi_cartesian_product(),
     )
 }
 
 fn part2() -> String {
     first_matching_serial_number(
-        iter::repeat(1..=9)
-            // 6 args
-            .take(6)
-            .multi_cartesian_product(),
+        // 6 args
+        iter::repeat_n(1..=9, 6).multi_cartesian_product(),
     )
 }
 
@@ -147,34 +161,32 @@ fn first_matching_serial_number(
     panic!("No valid program")
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn day24() {
-        let (part1, part2) = solve().unwrap();
-        assert_eq!(part1, "96918996924991");
-        assert_eq!(part2, "91811241911641");
-    }
-
-    #[test]
-    fn numbers_seq() {
-        let mut numbers = iter::repeat((1..=9).rev())
-            .take(7)
-            .multi_cartesian_product();
-        assert_eq!(
-            numbers.next().unwrap(),
-            vec![9, 9, 9, 9, 9, 9, 9, 9, 9, 9, 9, 9, 9, 9]
-        );
-        assert_eq!(
-            numbers.next().unwrap(),
-            vec![9, 9, 9, 9, 9, 9, 9, 9, 9, 9, 9, 9, 9, 8]
-        );
-    }
-}
-
-const _INPUT: &str = "\
+/// Every ALU program repeats this block for each of the 14 digits, only the constants differ.
+/// The constraints in `optimized_program` hold for one program's constants, which the shape
+/// can't tell apart from another's.
+const ALU_BLOCK: [&str; 18] = [
+    "inp w",
+    "mul x 0",
+    "add x z",
+    "mod x 26",
+    "div z (1|26)",
+    r"add x -?\d+",
+    "eql x w",
+    "eql x 0",
+    "mul y 0",
+    "add y 25",
+    "mul y x",
+    "add y 1",
+    "mul z y",
+    "mul y 0",
+    "add y w",
+    r"add y -?\d+",
+    "mul y x",
+    "add z y",
+];
+
+/// The ALU program the constraints in `optimized_program` were derived from
+const INPUT: &str = "\
 // input1
 inp w
 // mul x 0
@@ -532,3 +544,28 @@ add z y
 // y2: (input14 + 6) * x
 // z14: (z13 / 26) * y1 + y2
 ";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn day24() {
+        let (part1, part2) = solve().unwrap();
+        assert_eq!(part1, "96918996924991");
+        assert_eq!(part2, "91811241911641");
+    }
+
+    #[test]
+    fn numbers_seq() {
+        let mut numbers = iter::repeat_n((1..=9).rev(), 7).multi_cartesian_product();
+        assert_eq!(
+            numbers.next().unwrap(),
+            vec![9, 9, 9, 9, 9, 9, 9, 9, 9, 9, 9, 9, 9, 9]
+        );
+        assert_eq!(
+            numbers.next().unwrap(),
+            vec![9, 9, 9, 9, 9, 9, 9, 9, 9, 9, 9, 9, 9, 8]
+        );
+    }
+}
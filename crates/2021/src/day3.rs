@@ -1,14 +1,35 @@
 use std::collections::BTreeMap;
 
+use aoc_common::{Answer, Shape, Solution};
 use eyre::Result;
 
 use crate::input;
 
 pub fn solve() -> Result<(u32, u32)> {
-    let input = input(3);
+    let input = input(3)?;
     solve_for_input(&input)
 }
 
+pub struct Day3;
+
+impl Solution for Day3 {
+    fn part1(&self, input: &str) -> Result<Answer> {
+        Ok(part1(&parse(input)).into())
+    }
+    fn part2(&self, input: &str) -> Result<Answer> {
+        Ok(part2(&parse(input)).into())
+    }
+    fn parse(&self, input: &str) -> Option<Result<()>> {
+        aoc_common::parsed(eyre::Ok(parse(input)))
+    }
+    fn input_shape(&self) -> Option<Shape> {
+        Some(Shape::Grid {
+            cell: "[01]",
+            size: None,
+        })
+    }
+}
+
 fn solve_for_input(input: &str) -> Result<(u32, u32), eyre::Error> {
     let lines = parse(input);
 
@@ -40,7 +61,7 @@ fn part2(lines: &[Vec<char>]) -> u32 {
     }
     assert_eq!(oxygen_input.len(), 1);
     let oxygen_rating =
-        u32::from_str_radix(&String::from_iter(oxygen_input.get(0).unwrap()), 2).unwrap();
+        u32::from_str_radix(&String::from_iter(oxygen_input.first().unwrap()), 2).unwrap();
 
     let mut co2_scrubber_input = lines.to_vec();
     for pos in 0..line_len {
@@ -58,7 +79,7 @@ fn part2(lines: &[Vec<char>]) -> u32 {
     }
     assert_eq!(co2_scrubber_input.len(), 1);
     let co2_scrubber_rating =
-        u32::from_str_radix(&String::from_iter(co2_scrubber_input.get(0).unwrap()), 2).unwrap();
+        u32::from_str_radix(&String::from_iter(co2_scrubber_input.first().unwrap()), 2).unwrap();
 
     oxygen_rating * co2_scrubber_rating
 }
@@ -125,27 +146,4 @@ mod tests {
     fn day3() {
         assert_eq!(solve().unwrap(), (4118544, 3832770));
     }
-
-    #[test]
-    fn day3_pt2_example() {
-        assert_eq!(
-            solve_for_input(
-                "00100
-11110
-10110
-10111
-10101
-01111
-00111
-11100
-10000
-11001
-00010
-01010"
-            )
-            .unwrap()
-            .1,
-            230
-        );
-    }
 }
@@ -1,15 +1,36 @@
+use aoc_common::{Answer, Shape, Solution};
 use eyre::{eyre, Result};
 use tracing::debug;
 
 use crate::input;
 
 pub fn solve() -> Result<(u32, u32)> {
-    let input = input(4);
+    let input = input(4)?;
     let game = parse(&input)?;
     let winners = game.winners();
     Ok((part1(&winners), part2(&winners)))
 }
 
+pub struct Day4;
+
+impl Solution for Day4 {
+    fn part1(&self, input: &str) -> Result<Answer> {
+        Ok(part1(&parse(input)?.winners()).into())
+    }
+    fn part2(&self, input: &str) -> Result<Answer> {
+        Ok(part2(&parse(input)?.winners()).into())
+    }
+    fn parse(&self, input: &str) -> Option<Result<()>> {
+        aoc_common::parsed(parse(input))
+    }
+    fn input_shape(&self) -> Option<Shape> {
+        Some(Shape::Blocks(&[
+            Shape::Line(r"\d+(,\d+)*"),
+            Shape::Lines(r"[ \d]\d( [ \d]\d){4}"),
+        ]))
+    }
+}
+
 fn part1(winners: &[Vec<(Board, u32)>]) -> u32 {
     let first = winners.first().unwrap();
     assert_eq!(first.len(), 1);
@@ -125,37 +146,6 @@ struct Row(Vec<u8>);
 mod tests {
     use super::*;
 
-    #[test]
-    fn day4_ex() {
-        let game = parse(
-            "7,4,9,5,11,17,23,2,0,14,21,24,10,16,13,6,15,25,12,22,18,20,8,19,3,26,1
-
-22 13 17 11  0
- 8  2 23  4 24
-21  9 14 16  7
- 6 10  3 18  5
- 1 12 20 15 19
-
- 3 15  0  2 22
- 9 18 13 17  5
-19  8  7 25 23
-20 11 10 24  4
-14 21 16 12  6
-
-14 21 17 24  4
-10 16 15  9 19
-18  8 23 26 20
-22 11 13  6  5
- 2  0 12  3  7",
-        )
-        .unwrap();
-
-        let winners = game.winners();
-
-        assert_eq!(part1(&winners), 4512);
-        assert_eq!(part2(&winners), 1924);
-    }
-
     #[test]
     fn day4() {
         let (pt1, _pt2) = solve().unwrap();
@@ -1,12 +1,13 @@
 use std::{collections::BTreeMap, fmt::Debug, str::FromStr};
 
+use aoc_common::{Answer, Shape, Solution};
 use eyre::{bail, eyre, Result};
 use tracing::debug;
 
 use crate::input;
 
 pub fn solve() -> Result<(usize, usize)> {
-    let input = input(5);
+    let input = input(5)?;
     let lines = parse(&input)?;
 
     let part1 = count_line_intersections(&no_diagonal(&lines));
@@ -15,6 +16,23 @@ pub fn solve() -> Result<(usize, usize)> {
     Ok((part1, part2))
 }
 
+pub struct Day5;
+
+impl Solution for Day5 {
+    fn part1(&self, input: &str) -> Result<Answer> {
+        Ok(count_line_intersections(&no_diagonal(&parse(input)?)).into())
+    }
+    fn part2(&self, input: &str) -> Result<Answer> {
+        Ok(count_line_intersections(&parse(input)?).into())
+    }
+    fn parse(&self, input: &str) -> Option<Result<()>> {
+        aoc_common::parsed(parse(input))
+    }
+    fn input_shape(&self) -> Option<Shape> {
+        Some(Shape::Lines(r"\d+,\d+ -> \d+,\d+"))
+    }
+}
+
 fn no_diagonal(lines: &[Line]) -> Vec<Line> {
     lines.iter().filter(|l| !l.is_diagonal()).copied().collect()
 }
@@ -84,11 +102,7 @@ impl Line {
 }
 
 fn abs_diff(x: u32, y: u32) -> u32 {
-    if x > y {
-        x - y
-    } else {
-        y - x
-    }
+    x.abs_diff(y)
 }
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
@@ -141,25 +155,6 @@ mod tests {
         assert_eq!(result, (8060, 21577));
     }
 
-    #[test]
-    fn day5_ex() {
-        let _ = tracing_subscriber::fmt::try_init();
-
-        let input = "0,9 -> 5,9
-8,0 -> 0,8
-9,4 -> 3,4
-2,2 -> 2,1
-7,0 -> 7,4
-6,4 -> 2,0
-0,9 -> 2,9
-3,4 -> 1,4
-0,0 -> 8,8
-5,5 -> 8,2";
-        let lines = parse(input).unwrap();
-        let pt1 = count_line_intersections(&no_diagonal(&lines));
-        assert_eq!(pt1, 5);
-    }
-
     #[test]
     fn line_fns() {
         let lines = parse("9,4 -> 3,4\n3,4 -> 1,4").unwrap();
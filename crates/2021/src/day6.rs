@@ -1,9 +1,10 @@
+use aoc_common::{Answer, Shape, Solution};
 use eyre::{Context, Result};
 
 use crate::input;
 
 pub fn solve() -> Result<(u128, u128)> {
-    let input = input(6);
+    let input = input(6)?;
     let mut fishies = parse(&input)?;
 
     for _ in 0..80 {
@@ -19,6 +20,31 @@ pub fn solve() -> Result<(u128, u128)> {
     Ok((part1, part2))
 }
 
+pub struct Day6;
+
+impl Solution for Day6 {
+    fn part1(&self, input: &str) -> Result<Answer> {
+        Ok(population_after(input, 80)?.into())
+    }
+    fn part2(&self, input: &str) -> Result<Answer> {
+        Ok(population_after(input, 256)?.into())
+    }
+    fn parse(&self, input: &str) -> Option<Result<()>> {
+        aoc_common::parsed(parse(input))
+    }
+    fn input_shape(&self) -> Option<Shape> {
+        Some(Shape::Line(r"\d(,\d)*"))
+    }
+}
+
+fn population_after(input: &str, days: usize) -> Result<u128> {
+    let mut fishies = parse(input)?;
+    for _ in 0..days {
+        fishies.step();
+    }
+    Ok(fishies.sum())
+}
+
 struct FishBuckets([u128; 9]);
 
 impl FishBuckets {
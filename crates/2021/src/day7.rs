@@ -1,9 +1,10 @@
+use aoc_common::{Answer, Shape, Solution};
 use eyre::Result;
 
 use crate::input;
 
 pub fn solve() -> Result<(usize, usize)> {
-    let input = input(7);
+    let input = input(7)?;
     let positions = parse(&input)?;
 
     let part1 = search_best(&positions, fuel_cost_pt1);
@@ -12,6 +13,23 @@ pub fn solve() -> Result<(usize, usize)> {
     Ok((part1, part2))
 }
 
+pub struct Day7;
+
+impl Solution for Day7 {
+    fn part1(&self, input: &str) -> Result<Answer> {
+        Ok(search_best(&parse(input)?, fuel_cost_pt1).into())
+    }
+    fn part2(&self, input: &str) -> Result<Answer> {
+        Ok(search_best(&parse(input)?, fuel_cost_pt2).into())
+    }
+    fn parse(&self, input: &str) -> Option<Result<()>> {
+        aoc_common::parsed(parse(input))
+    }
+    fn input_shape(&self) -> Option<Shape> {
+        Some(Shape::Line(r"\d+(,\d+)*"))
+    }
+}
+
 fn search_best(positions: &[u16], fuel_cost_fn: impl Fn(&[u16], usize) -> usize) -> usize {
     let mean_pos = positions.iter().map(|x| *x as usize).sum::<usize>() / positions.len();
     let cost = fuel_cost_fn(positions, mean_pos);
@@ -60,11 +78,7 @@ fn fuel_cost_pt2(positions: &[u16], to: usize) -> usize {
 
 fn abs_diff(x: u16, y: usize) -> usize {
     let x = usize::from(x);
-    if x > y {
-        x - y
-    } else {
-        y - x
-    }
+    x.abs_diff(y)
 }
 
 fn parse(input: &str) -> Result<Vec<u16>> {
@@ -1,16 +1,34 @@
 use std::{collections::BTreeMap, str::FromStr};
 
+use aoc_common::{Answer, Shape, Solution};
 use eyre::{eyre, Result};
 
 use crate::input;
 
 pub fn solve() -> Result<(usize, u32)> {
-    let input = input(8);
+    let input = input(8)?;
     let displays = parse(&input)?;
 
     Ok((part1(&displays), part2(&displays)))
 }
 
+pub struct Day8;
+
+impl Solution for Day8 {
+    fn part1(&self, input: &str) -> Result<Answer> {
+        Ok(part1(&parse(input)?).into())
+    }
+    fn part2(&self, input: &str) -> Result<Answer> {
+        Ok(part2(&parse(input)?).into())
+    }
+    fn parse(&self, input: &str) -> Option<Result<()>> {
+        aoc_common::parsed(parse(input))
+    }
+    fn input_shape(&self) -> Option<Shape> {
+        Some(Shape::Lines(r"[a-g]+( [a-g]+){9} \| [a-g]+( [a-g]+){3}"))
+    }
+}
+
 fn part1(displays: &[Display]) -> usize {
     displays.iter().map(|d| d.obvious_numbers().len()).sum()
 }
@@ -173,12 +191,10 @@ impl FromStr for Display {
             .split_once('|')
             .ok_or_else(|| eyre!("No pipe found in {:?}", s))?;
         let signal_patterns = signal_patterns
-            .trim()
             .split_whitespace()
             .map(|s| Signal(sort_string(s)))
             .collect();
         let output = output
-            .trim()
             .split_whitespace()
             .map(|s| Signal(sort_string(s)))
             .collect();
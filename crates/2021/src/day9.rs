@@ -1,18 +1,39 @@
 use std::{collections::BTreeSet, str::FromStr};
 
+use aoc_common::{Answer, Coord, Grid, Shape, Solution};
 use eyre::Result;
 use tracing::debug;
 
 use crate::input;
 
 pub fn solve() -> Result<(u64, u64)> {
-    let input = input(9);
+    let input = input(9)?;
     let mut heightmap = HeightMap::from_str(&input)?;
     let lowest = heightmap.lowest_points();
 
     Ok((part1(&lowest), part2(&heightmap)))
 }
 
+pub struct Day9;
+
+impl Solution for Day9 {
+    fn part1(&self, input: &str) -> Result<Answer> {
+        let mut heightmap = HeightMap::from_str(input)?;
+        Ok(part1(&heightmap.lowest_points()).into())
+    }
+    fn part2(&self, input: &str) -> Result<Answer> {
+        let mut heightmap = HeightMap::from_str(input)?;
+        heightmap.lowest_points();
+        Ok(part2(&heightmap).into())
+    }
+    fn input_shape(&self) -> Option<Shape> {
+        Some(Shape::Grid {
+            cell: "[0-9]",
+            size: None,
+        })
+    }
+}
+
 fn part1(lowest_points: &[u8]) -> u64 {
     lowest_points.iter().map(|lowest| *lowest as u64 + 1).sum()
 }
@@ -29,8 +50,8 @@ struct Basin {
 }
 
 struct HeightMap {
-    map: Vec<Vec<u8>>,
-    lowest_points: Vec<(usize, usize)>,
+    map: Grid<u8>,
+    lowest_points: Vec<Coord>,
 }
 
 impl HeightMap {
@@ -46,13 +67,13 @@ impl HeightMap {
                 debug!("Trying from start {:?} edge {:?}", starting_point, edge);
                 let old_edge = edge;
                 let mut new_edge = BTreeSet::new();
-                for (x, y) in old_edge {
-                    for (x1, y1) in self.neighbor_coords(x, y) {
-                        if self.at(x1, y1) == Some(9) {
+                for coord in old_edge {
+                    for neighbor in self.map.neighbors4(coord) {
+                        if self.map[neighbor] == 9 {
                             continue;
                         }
-                        if points_in_basin.insert((x1, y1)) {
-                            new_edge.insert((x1, y1));
+                        if points_in_basin.insert(neighbor) {
+                            new_edge.insert(neighbor);
                         }
                     }
                 }
@@ -74,64 +95,27 @@ impl HeightMap {
     }
     fn lowest_points(&mut self) -> Vec<u8> {
         let mut lowest = Vec::new();
-        for x in 0..self.height() {
-            for y in 0..self.width() {
-                let point = self.at(x, y).unwrap();
-                let neighbors = self.neighbors(x, y);
-                if neighbors.iter().all(|neighbor| point < *neighbor) {
-                    // eprintln!("Lowest at ({},{}) ({} < {:?})", x, y, point, neighbors);
-                    self.lowest_points.push((x, y));
-                    lowest.push(point);
-                }
+        for (coord, &point) in self.map.iter() {
+            if self
+                .map
+                .neighbors4(coord)
+                .all(|neighbor| point < self.map[neighbor])
+            {
+                // eprintln!("Lowest at {:?} ({} < {:?})", coord, point, neighbors);
+                self.lowest_points.push(coord);
+                lowest.push(point);
             }
         }
         lowest
     }
-    fn height(&self) -> usize {
-        self.map.len()
-    }
-    fn width(&self) -> usize {
-        self.map[0].len()
-    }
-    fn neighbors(&self, x: usize, y: usize) -> Vec<u8> {
-        let mut neighbors = Vec::with_capacity(4);
-        for (x1, y1) in self.neighbor_coords(x, y) {
-            neighbors.extend(self.at(x1, y1))
-        }
-        neighbors
-    }
-    fn neighbor_coords(&self, x: usize, y: usize) -> Vec<(usize, usize)> {
-        let mut coords = Vec::with_capacity(4);
-        if x != 0 {
-            coords.push((x - 1, y));
-        }
-        if x != self.height() - 1 {
-            coords.push((x + 1, y));
-        }
-        if y != 0 {
-            coords.push((x, y - 1));
-        }
-        if y != self.width() - 1 {
-            coords.push((x, y + 1))
-        }
-
-        coords
-    }
-    fn at(&self, x: usize, y: usize) -> Option<u8> {
-        self.map.get(x)?.get(y).copied()
-    }
 }
 
 impl FromStr for HeightMap {
     type Err = eyre::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let map = s
-            .lines()
-            .map(|l| l.chars().map(|c| c as u8 - b'0').collect())
-            .collect();
         Ok(HeightMap {
-            map,
+            map: Grid::parse_digits(s)?,
             lowest_points: Vec::new(),
         })
     }
@@ -147,17 +131,4 @@ mod tests {
         assert_eq!(part1, 496);
         assert!(part2 > 30603);
     }
-
-    #[test]
-    fn day9_ex() {
-        let input = "2199943210
-3987894921
-9856789892
-8767896789
-9899965678";
-        let mut m = HeightMap::from_str(input).unwrap();
-        let part1 = part1(&m.lowest_points());
-        assert_eq!(part1, 15);
-        assert_eq!(part2(&m), 1134);
-    }
 }
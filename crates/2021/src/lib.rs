@@ -0,0 +1,47 @@
+pub mod day1;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day2;
+pub mod day24;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+
+use aoc_common::{InputError, Puzzle};
+
+pub const PUZZLES: &[Puzzle] = &[
+    Puzzle::new(2021, 1, &day1::Day1),
+    Puzzle::new(2021, 2, &day2::Day2),
+    Puzzle::new(2021, 3, &day3::Day3),
+    Puzzle::new(2021, 4, &day4::Day4),
+    Puzzle::new(2021, 5, &day5::Day5),
+    Puzzle::new(2021, 6, &day6::Day6),
+    Puzzle::new(2021, 7, &day7::Day7),
+    Puzzle::new(2021, 8, &day8::Day8),
+    Puzzle::new(2021, 9, &day9::Day9),
+    Puzzle::new(2021, 10, &day10::Day10),
+    Puzzle::new(2021, 11, &day11::Day11),
+    Puzzle::new(2021, 12, &day12::Day12),
+    Puzzle::new(2021, 13, &day13::Day13),
+    Puzzle::new(2021, 14, &day14::Day14),
+    Puzzle::new(2021, 15, &day15::Day15),
+    Puzzle::new(2021, 16, &day16::Day16),
+    Puzzle::new(2021, 17, &day17::Day17),
+    Puzzle::new(2021, 18, &day18::Day18),
+    Puzzle::new(2021, 24, &day24::Day24),
+];
+
+pub fn input(day: u32) -> Result<String, InputError> {
+    aoc_common::read_input(2021, day)
+}
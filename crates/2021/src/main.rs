@@ -1,24 +1,4 @@
-use std::fs;
-
-mod day1;
-mod day10;
-mod day11;
-mod day12;
-mod day13;
-mod day14;
-mod day15;
-mod day16;
-mod day17;
-mod day18;
-mod day2;
-mod day24;
-mod day3;
-mod day4;
-mod day5;
-mod day6;
-mod day7;
-mod day8;
-mod day9;
+use aoc2021::*;
 
 fn main() {
     tracing_subscriber::fmt::init();
@@ -44,8 +24,3 @@ fn main() {
     println!("day18: {:?}", day18::solve().unwrap());
     println!("day24: {:?}", day24::solve().unwrap());
 }
-
-fn input(day: usize) -> String {
-    let path = format!("input{}.txt", day);
-    fs::read_to_string(&path).unwrap_or_else(|e| panic!("Failed to read {}: {}", path, e))
-}
@@ -1,55 +1,60 @@
+use aoc_common::{parse, Answer, ParseError, Shape, Solution};
+use eyre::Result;
+
 use crate::input;
 
-pub fn solve() -> (u32, u32) {
-    let input = input(1);
-    (part1(&input), part2(&input))
+pub fn solve() -> Result<(u32, u32)> {
+    let input = input(1)?;
+    Ok((part1(&input)?, part2(&input)?))
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    fn part1(&self, input: &str) -> eyre::Result<Answer> {
+        Ok(part1(input)?.into())
+    }
+    fn part2(&self, input: &str) -> eyre::Result<Answer> {
+        Ok(part2(input)?.into())
+    }
+    fn input_shape(&self) -> Option<Shape> {
+        Some(Shape::Blocks(&[Shape::Lines(r"\d+")]))
+    }
 }
 
-fn part1(input: &str) -> u32 {
-    inventories_sorted(input)[0]
+fn part1(input: &str) -> Result<u32, ParseError> {
+    Ok(inventories_sorted(input)?[0])
 }
 
-fn part2(input: &str) -> u32 {
-    inventories_sorted(input)[..3].iter().sum()
+fn part2(input: &str) -> Result<u32, ParseError> {
+    Ok(inventories_sorted(input)?[..3].iter().sum())
 }
 
-fn inventories_sorted(input: &str) -> Vec<u32> {
+fn inventories_sorted(input: &str) -> Result<Vec<u32>, ParseError> {
     let mut inventories = Vec::new();
-    input.lines().fold(0, |calories_so_far, food| {
+    let last = input.lines().try_fold(0, |calories_so_far, food| {
         let food = food.trim();
         if food.is_empty() {
             inventories.push(calories_so_far);
-            0
+            Ok(0)
         } else {
-            calories_so_far + food.parse::<u32>().unwrap()
+            parse::number::<u32>(food).map(|calories| calories_so_far + calories)
         }
     });
+    inventories.push(last.map_err(|e| e.locate(1, input))?);
     inventories.sort_unstable();
     inventories.reverse();
-    inventories
+    Ok(inventories)
 }
 
 #[test]
-fn example() {
-    let input = r"1000
-2000
-3000
-
-4000
-
-5000
-6000
-
-7000
-8000
-9000
-
-10000
-";
-    assert_eq!(part1(input), 24000);
+fn day1() {
+    assert_eq!(solve().unwrap(), (74711, 209481));
 }
 
 #[test]
-fn day1() {
-    assert_eq!(solve(), (74711, 209481));
+fn bad_calories() {
+    let error = part1("1000\n2000\n\n30o0\n").unwrap_err();
+    assert_eq!((error.day, error.line, error.column), (1, 4, 1));
+    assert_eq!(error.text, "30o0");
 }
@@ -1,38 +1,67 @@
 use std::{collections::VecDeque, iter, str::FromStr};
 
-use tracing::debug;
+use aoc_common::{ocr, parse, Answer, ParseError, Shape, Solution};
+use eyre::Result;
+use tracing::{debug, trace};
 
 use crate::input;
 
-pub fn solve() -> (i64, String) {
-    let input = input(10);
-    (part1(&input), part2(&input))
+pub fn solve() -> Result<(i64, String)> {
+    let input = input(10)?;
+    Ok((part1(&input)?, part2(&input)?))
 }
 
-fn part1(input: &str) -> i64 {
+pub struct Day10;
+
+impl Solution for Day10 {
+    fn part1(&self, input: &str) -> Result<Answer> {
+        Ok(part1(input)?.into())
+    }
+    fn part2(&self, input: &str) -> Result<Answer> {
+        Ok(part2(input)?.into())
+    }
+    fn parse(&self, input: &str) -> Option<Result<()>> {
+        aoc_common::parsed(parse(input))
+    }
+    fn input_shape(&self) -> Option<Shape> {
+        Some(Shape::Lines(r"noop|addx -?\d+"))
+    }
+}
+
+fn part1(input: &str) -> Result<i64, ParseError> {
     let cpu = Cpu::default();
-    let ops = parse(input);
-    cpu.run(ops)
+    let ops = parse(input)?;
+    Ok(cpu
+        .run(ops)
         .filter(|cpu| (cpu.tick as i32 - 20) % 40 == 0)
         .map(|cpu| {
             let result = cpu.tick as i64 * cpu.register_x as i64;
             debug!(?result, ?cpu);
             result
         })
-        .sum()
+        .sum())
 }
 
-fn part2(input: &str) -> String {
+fn part2(input: &str) -> Result<String> {
+    let screen = render(input)?;
+    debug!("screen:\n{screen}");
+    Ok(ocr::read_letters(&screen)?)
+}
+
+fn render(input: &str) -> Result<String, ParseError> {
     let cpu = Cpu::default();
-    let ops = parse(input);
+    let ops = parse(input)?;
     let mut states = cpu.run(ops);
     let mut result = Vec::new();
     for _ in 0..6 {
         let mut line = String::new();
         for col in 0..40 {
-            let state = states.next().unwrap();
+            let state = states.next().ok_or_else(|| {
+                ParseError::missing(input.trim_end(), "enough instructions for 240 cycles")
+                    .locate(10, input)
+            })?;
             match state.register_x - col {
-                -1 | 0 | 1 => {
+                -1..=1 => {
                     line.push('#');
                 }
                 _ => {
@@ -42,15 +71,11 @@ fn part2(input: &str) -> String {
         }
         result.push(line)
     }
-    result.join("\n")
+    Ok(result.join("\n"))
 }
 
-fn parse(input: &str) -> VecDeque<Op> {
-    input
-        .lines()
-        .map(Op::from_str)
-        .collect::<Result<VecDeque<_>, _>>()
-        .unwrap()
+fn parse(input: &str) -> Result<VecDeque<Op>, ParseError> {
+    parse::lines(10, input).map(VecDeque::from)
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -59,17 +84,19 @@ enum Op {
     AddX(i32),
 }
 impl FromStr for Op {
-    type Err = !;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(if s.starts_with("noop") {
-            Op::Noop
-        } else if s.starts_with("addx") {
-            let (_, amount) = s.split_once(' ').unwrap();
-            Op::AddX(amount.parse().unwrap())
+        if s == "noop" {
+            Ok(Op::Noop)
+        } else if let Some(amount) = s.strip_prefix("addx") {
+            match amount.strip_prefix(' ') {
+                Some(amount) => Ok(Op::AddX(parse::number(amount)?)),
+                None => Err(ParseError::missing(s, "an amount to add")),
+            }
         } else {
-            panic!("Illegal op {s:?}")
-        })
+            Err(ParseError::new(s, "expected noop or addx"))
+        }
     }
 }
 
@@ -87,31 +114,26 @@ impl Cpu {
             // start cycle
             if cpu.op.is_none() {
                 cpu.op = ops.pop_front().map(|o| (0, o));
-                // debug!(next_op = ?cpu.op);
-            }
-            if cpu.op.is_none() {
-                None
-            } else {
-                let result = cpu;
-                cpu.tick();
-                Some(result)
+                trace!(next_op = ?cpu.op, tick = cpu.tick);
             }
+            let (count, op) = cpu.op?;
+            let result = cpu;
+            cpu.tick(count, op);
+            Some(result)
         })
     }
-    fn tick(&mut self) {
+    /// Ends a cycle that spent `count` cycles on `op` before.
+    fn tick(&mut self, count: usize, op: Op) {
         self.tick += 1;
-        match &mut self.op {
-            Some((_, Op::Noop)) => self.op = None,
-            Some((count, Op::AddX(i))) => {
-                *count += 1;
-                if *count >= 2 {
-                    // goes into effect at the end
-                    self.register_x += *i;
-                    self.op = None;
-                }
+        self.op = match op {
+            Op::Noop => None,
+            // goes into effect at the end
+            Op::AddX(i) if count + 1 >= 2 => {
+                self.register_x += i;
+                None
             }
-            None => panic!("No op in tick"),
-        }
+            Op::AddX(_) => Some((count + 1, op)),
+        };
     }
 }
 
@@ -275,8 +297,8 @@ noop
 noop
 noop";
 
-    assert_eq!(part1(input), 13140);
-    let pt2 = part2(input);
+    assert_eq!(part1(input).unwrap(), 13140);
+    let pt2 = render(input).unwrap();
     println!("{pt2}");
     assert_eq!(
         pt2,
@@ -290,18 +312,21 @@ noop";
 }
 
 #[test]
-fn day10() {
+fn truncated() {
+    let error = parse("noop\naddx 3\naddx").unwrap_err();
     assert_eq!(
-        solve(),
-        (
-            11960,
-            "####...##..##..####.###...##..#....#..#.
-#.......#.#..#.#....#..#.#..#.#....#..#.
-###.....#.#....###..#..#.#....#....####.
-#.......#.#....#....###..#.##.#....#..#.
-#....#..#.#..#.#....#....#..#.#....#..#.
-####..##...##..#....#.....###.####.#..#."
-                .to_string()
-        )
-    )
+        error.to_string(),
+        "day 10, line 3, column 5: expected an amount to add, found nothing"
+    );
+    let error = render("noop\naddx 3\n").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "day 10, line 2, column 7: expected enough instructions for 240 cycles, found nothing"
+    );
+    assert_eq!(Cpu::default().run(VecDeque::new()).count(), 0);
+}
+
+#[test]
+fn day10() {
+    assert_eq!(solve().unwrap(), (11960, "EJCFPGLH".to_string()))
 }
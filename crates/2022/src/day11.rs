@@ -1,43 +1,64 @@
 use std::{cmp, str::FromStr};
 
-use itertools::Itertools;
+use aoc_common::{parse, Answer, ParseError, Shape, Solution};
+use eyre::{eyre, Result};
+use tracing::{instrument, trace};
 
 use crate::input;
 
-pub fn solve() -> (u64, u64) {
-    let input = input(11);
-    (part1(&input), part2(&input))
+pub fn solve() -> Result<(u64, u64)> {
+    let input = input(11)?;
+    let monkeys = parse(&input)?;
+    Ok((part1(monkeys.clone())?, part2(monkeys)?))
 }
 
-fn part1(input: &str) -> u64 {
+pub struct Day11;
+
+impl Solution for Day11 {
+    fn part1(&self, input: &str) -> Result<Answer> {
+        Ok(part1(parse(input)?)?.into())
+    }
+    fn part2(&self, input: &str) -> Result<Answer> {
+        Ok(part2(parse(input)?)?.into())
+    }
+    fn parse(&self, input: &str) -> Option<Result<()>> {
+        aoc_common::parsed(parse(input))
+    }
+    fn input_shape(&self) -> Option<Shape> {
+        Some(Shape::Blocks(&[Shape::Lines(
+            r"Monkey \d+:|  Starting items: (\d+(, \d+)*)?|  Operation: new = old [*+] (old|\d+)|  Test: divisible by \d+|    If (true|false): throw to monkey \d+",
+        )]))
+    }
+}
+
+fn part1(monkeys: Vec<Monkey>) -> Result<u64> {
     let mut monkeys = MonkeyBusiness {
-        monkeys: parse(&input),
+        monkeys,
         relief_factor: ReliefFactor::Div(3),
     };
-    for _ in 0..20 {
-        monkeys.round();
+    for round in 0..20 {
+        monkeys.round(round)?;
     }
     let (most_active, second_most_active) = monkeys.most_active_monkeys();
-    most_active * second_most_active
+    Ok(most_active * second_most_active)
 }
 
-fn part2(input: &str) -> u64 {
-    let monkeys = parse(&input);
+fn part2(monkeys: Vec<Monkey>) -> Result<u64> {
     let lcm = monkeys.iter().map(|m| m.test.divisor).product();
     let mut monkeys = MonkeyBusiness {
         monkeys,
         relief_factor: ReliefFactor::Modulo(lcm),
     };
-    for _ in 0..10_000 {
-        monkeys.round();
+    for round in 0..10_000 {
+        monkeys.round(round)?;
     }
     let (most_active, second_most_active) = monkeys.most_active_monkeys();
-    most_active * second_most_active
+    Ok(most_active * second_most_active)
 }
 
 type Item = u64;
 
-#[derive(Clone)]
+#[derive(Clone, Debug)]
 struct Monkey {
     id: MonkeyId,
     items: Vec<Item>,
@@ -53,15 +74,14 @@ struct Operation {
     r: Operand,
 }
 impl Operation {
-    fn run(&self, item: Item) -> Item {
+    fn run(&self, item: Item) -> Result<Item> {
         let l = self.l.value(item);
         let r = self.r.value(item);
-        match self.op {
-            Operator::Add => l.checked_add(r).unwrap(),
-            Operator::Mul => l
-                .checked_mul(r)
-                .unwrap_or_else(|| panic!("{l} * {r} overflows")),
-        }
+        let result = match self.op {
+            Operator::Add => l.checked_add(r),
+            Operator::Mul => l.checked_mul(r),
+        };
+        result.ok_or_else(|| eyre!("{l} {:?} {r} overflows", self.op))
     }
 }
 
@@ -104,7 +124,7 @@ impl std::fmt::Debug for Test {
 }
 impl Test {
     fn run(&self, item: &Item) -> MonkeyId {
-        if item % &self.divisor == 0 {
+        if item.is_multiple_of(self.divisor) {
             self.if_true
         } else {
             self.if_false
@@ -113,21 +133,26 @@ impl Test {
 }
 
 impl Monkey {
-    fn turn(&mut self, relief_factor: ReliefFactor) -> Vec<(Item, MonkeyId)> {
+    fn turn(&mut self, relief_factor: ReliefFactor) -> Result<Vec<(Item, MonkeyId)>> {
         self.items
             .drain(..)
             .map(|item| {
-                let inspected = self.op.run(item);
+                let inspected = self.op.run(item)?;
                 let bored_relieved = relief_factor.run(inspected);
                 self.monkey_business += 1;
                 let target = self.test.run(&bored_relieved);
-                // debug!(
-                //     "monkey {} inspects {}, {:?} = {}, /3 = {}, {:?} -> {}",
-                //     self.id, item, self.op, inspected, bored_relieved, self.test, target
-                // );
-                (bored_relieved, target)
+                trace!(
+                    monkey = self.id,
+                    item,
+                    op = ?self.op,
+                    inspected,
+                    bored_relieved,
+                    test = ?self.test,
+                    target
+                );
+                Ok((bored_relieved, target))
             })
-            .collect_vec()
+            .collect()
     }
 }
 
@@ -136,15 +161,17 @@ struct MonkeyBusiness {
     relief_factor: ReliefFactor,
 }
 impl MonkeyBusiness {
-    fn round(&mut self) {
+    #[instrument(level = "debug", skip(self))]
+    fn round(&mut self, round: u32) -> Result<()> {
         for i in 0..self.monkeys.len() {
-            let items = self.monkeys[i].turn(self.relief_factor);
+            let items = self.monkeys[i].turn(self.relief_factor)?;
             for (item, target_id) in items {
                 let target = &mut self.monkeys[target_id as usize];
                 assert_eq!(target.id, target_id);
                 target.items.push(item);
             }
         }
+        Ok(())
     }
     fn most_active_monkeys(&self) -> (u64, u64) {
         let mut monkeys = self.monkeys.clone();
@@ -154,81 +181,67 @@ impl MonkeyBusiness {
     }
 }
 
-fn parse(input: &str) -> Vec<Monkey> {
+fn parse(input: &str) -> Result<Vec<Monkey>, ParseError> {
     input
         .split("\n\n")
-        .map(|block| Monkey::from_str(block).unwrap())
+        .map(|block| block.parse().map_err(|e: ParseError| e.locate(11, input)))
         .collect()
 }
 
 impl FromStr for Monkey {
-    type Err = !;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut lines = s.lines();
+        let mut lines = s.lines().map(str::trim);
+        let mut line = |what: &str| {
+            lines
+                .next()
+                .ok_or_else(|| ParseError::missing(s.trim_end(), what))
+        };
         let id: u32 = {
-            let (_monkey, id) = lines.next().unwrap().split_once(' ').unwrap();
-            let id = id.strip_suffix(':').unwrap();
-            id.parse().unwrap()
+            let id = parse::strip_prefix(line("a monkey")?, "Monkey ")?;
+            let id = id
+                .strip_suffix(':')
+                .ok_or_else(|| ParseError::missing(id, "\":\""))?;
+            parse::number(id)?
         };
         let items: Vec<_> = {
-            let (_, items) = lines.next().unwrap().split_once(':').unwrap();
+            let items = parse::strip_prefix(line("starting items")?, "Starting items: ")?;
             items
                 .split(',')
-                .map(|i| i.trim().parse::<Item>().unwrap())
-                .collect()
+                .map(|i| parse::number::<Item>(i.trim()))
+                .collect::<Result<_, _>>()?
         };
 
         let op = {
-            let op = lines
-                .next()
-                .unwrap()
-                .trim()
-                .strip_prefix("Operation: ")
-                .unwrap();
-            let mut expr = op.strip_prefix("new = ").unwrap().split_whitespace();
+            let op = parse::strip_prefix(line("an operation")?, "Operation: new = ")?;
+            let mut expr = op.split_whitespace();
+            let mut next = |what| expr.next().ok_or_else(|| ParseError::missing(op, what));
             let operand = |s: &str| match s {
-                "old" => Operand::Old,
-                num => {
-                    let num = num.parse::<Item>().unwrap();
-                    Operand::Lit(num)
-                }
+                "old" => Ok(Operand::Old),
+                num => parse::number(num).map(Operand::Lit),
             };
-            let l = operand(expr.next().unwrap());
-            let op = match expr.next().unwrap() {
+            let l = operand(next("an operand")?)?;
+            let op = match next("an operator")? {
                 "+" => Operator::Add,
                 "*" => Operator::Mul,
-                other => panic!("Invalid op {other:?}"),
+                other => return Err(ParseError::new(other, "expected + or *")),
             };
-            let r = operand(expr.next().unwrap());
+            let r = operand(next("an operand")?)?;
             Operation { l, op, r }
         };
 
         let test = {
-            let divisor = lines
-                .next()
-                .unwrap()
-                .trim()
-                .strip_prefix("Test: divisible by ")
-                .unwrap()
-                .parse::<Item>()
-                .unwrap();
-            let if_true = lines
-                .next()
-                .unwrap()
-                .trim()
-                .strip_prefix("If true: throw to monkey ")
-                .unwrap()
-                .parse::<MonkeyId>()
-                .unwrap();
-            let if_false = lines
-                .next()
-                .unwrap()
-                .trim()
-                .strip_prefix("If false: throw to monkey ")
-                .unwrap()
-                .parse::<MonkeyId>()
-                .unwrap();
+            let divisor =
+                parse::number(parse::strip_prefix(line("a test")?, "Test: divisible by ")?)?;
+            let if_true = parse::number(parse::strip_prefix(
+                line("a target if true")?,
+                "If true: throw to monkey ",
+            )?)?;
+            let if_false = parse::number(parse::strip_prefix(
+                line("a target if false")?,
+                "If false: throw to monkey ",
+            )?)?;
             Test {
                 divisor,
                 if_true,
@@ -287,7 +300,7 @@ impl Operand {
 type MonkeyId = u32;
 
 #[test]
-fn example() {
+fn truncated() {
     let input = "Monkey 0:
   Starting items: 79, 98
   Operation: new = old * 19
@@ -297,23 +310,29 @@ fn example() {
 
 Monkey 1:
   Starting items: 54, 65, 75, 74
-  Operation: new = old + 6
-  Test: divisible by 19
-    If true: throw to monkey 2
-    If false: throw to monkey 0
-
-Monkey 2:
-  Starting items: 79, 60, 97
-  Operation: new = old * old
-  Test: divisible by 13
-    If true: throw to monkey 1
-    If false: throw to monkey 3
+  Operation: new = old +
+";
+    let error = parse(input).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "day 11, line 10, column 25: expected an operand, found nothing"
+    );
+    let error = parse(&input.replace("54, 65", "54, x5")).unwrap_err();
+    assert_eq!(
+        (error.line, error.column, error.text.as_str()),
+        (9, 23, "x5")
+    );
+}
 
-Monkey 3:
-  Starting items: 74
-  Operation: new = old + 3
-  Test: divisible by 17
+#[test]
+fn overflow() {
+    let input = "Monkey 0:
+  Starting items: 18446744073709551615
+  Operation: new = old + 1
+  Test: divisible by 2
     If true: throw to monkey 0
-    If false: throw to monkey 1";
-    assert_eq!(part1(input), 10605);
+    If false: throw to monkey 0
+";
+    let error = part1(parse(input).unwrap()).unwrap_err();
+    assert_eq!(error.to_string(), "18446744073709551615 + 1 overflows");
 }
@@ -1,4 +1,6 @@
 use crate::input;
+use aoc_common::{parse, Answer, ParseError, Shape, Solution};
+use eyre::Result;
 use tracing::instrument;
 
 #[derive(Clone, Copy)]
@@ -12,14 +14,14 @@ use std::str::FromStr;
 use GameResult::*;
 
 impl FromStr for GameResult {
-    type Err = !;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         Ok(match s {
             "X" => Lose,
             "Y" => Draw,
             "Z" => Win,
-            _ => panic!("no GameResult: {s}"),
+            _ => return Err(ParseError::new(s, "expected X, Y or Z")),
         })
     }
 }
@@ -60,14 +62,14 @@ impl Rps {
     }
 }
 impl FromStr for Rps {
-    type Err = !;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         Ok(match s {
             "A" | "X" => Rock,
             "B" | "Y" => Paper,
             "C" | "Z" => Scissors,
-            _ => panic!("no RPS: {s}"),
+            _ => return Err(ParseError::new(s, "expected A, B, C, X, Y or Z")),
         })
     }
 }
@@ -85,41 +87,61 @@ fn required_move(other: Rps, result: GameResult) -> Rps {
     }
 }
 
-pub fn solve() -> (u32, u32) {
-    let input = input(2);
-    (part1(&input), part2(&input))
+pub fn solve() -> Result<(u32, u32)> {
+    let input = input(2)?;
+    Ok((part1(&input)?, part2(&input)?))
 }
 
-fn part1(input: &str) -> u32 {
-    input
-        .lines()
-        .map(|line| parse_line(line, |s| s.parse::<Rps>().unwrap()))
+pub struct Day2;
+
+impl Solution for Day2 {
+    fn part1(&self, input: &str) -> Result<Answer> {
+        Ok(part1(input)?.into())
+    }
+    fn part2(&self, input: &str) -> Result<Answer> {
+        Ok(part2(input)?.into())
+    }
+    fn input_shape(&self) -> Option<Shape> {
+        Some(Shape::Lines("[ABC] [XYZ]"))
+    }
+}
+
+fn part1(input: &str) -> Result<u32, ParseError> {
+    Ok(parse::<Rps>(input)?
+        .into_iter()
         .map(|(other, you)| score(you, other))
-        .sum()
+        .sum())
 }
-fn part2(input: &str) -> u32 {
-    input
-        .lines()
-        .map(|line| parse_line(line, |s| s.parse::<GameResult>().unwrap()))
+fn part2(input: &str) -> Result<u32, ParseError> {
+    Ok(parse::<GameResult>(input)?
+        .into_iter()
         .map(|(other, result)| score(required_move(other, result), other))
-        .sum()
+        .sum())
 }
 
-fn parse_line<O>(line: &str, second_parse_fn: impl Fn(&str) -> O) -> (Rps, O) {
-    let (other, you) = line.split_once(' ').unwrap();
-    (other.parse().unwrap(), second_parse_fn(you))
+/// The second column means a move in part 1 and the result of the round in part 2.
+fn parse<O: FromStr<Err = ParseError>>(input: &str) -> Result<Vec<(Rps, O)>, ParseError> {
+    input
+        .lines()
+        .map(|line| {
+            let (other, you) = parse::split_once(line, " ")?;
+            Ok((other.parse()?, you.parse()?))
+        })
+        .collect::<Result<_, ParseError>>()
+        .map_err(|e| e.locate(2, input))
 }
 
 #[test]
-fn example() {
-    crate::init();
-    let input = "A Y
-B X
-C Z";
-    assert_eq!(part1(input), 15);
+fn day2() {
+    assert_eq!(solve().unwrap(), (13924, 13448));
 }
 
 #[test]
-fn day2() {
-    assert_eq!(solve(), (13924, 13448));
+fn bad_move() {
+    let error = part2("A Y\nB W\n").unwrap_err();
+    assert_eq!((error.line, error.column), (2, 3));
+    assert_eq!(
+        error.to_string(),
+        r#"day 2, line 2, column 3: expected X, Y or Z, found "W""#
+    );
 }
@@ -1,55 +1,102 @@
 use std::collections::HashSet;
 
-use tap::Pipe;
+use aoc_common::{Answer, ParseError, Shape, Solution};
+use eyre::Result;
 
 use crate::input;
 
-pub fn solve() -> (u32, u32) {
-    let input = input(3);
-    (part1(&input), part2(&input))
+pub fn solve() -> Result<(u32, u32)> {
+    let input = input(3)?;
+    Ok((part1(&input)?, part2(&input)?))
 }
 
-fn part1(input: &str) -> u32 {
-    parse_compartments(input)
+pub struct Day3;
+
+impl Solution for Day3 {
+    fn part1(&self, input: &str) -> Result<Answer> {
+        Ok(part1(input)?.into())
+    }
+    fn part2(&self, input: &str) -> Result<Answer> {
+        Ok(part2(input)?.into())
+    }
+    fn input_shape(&self) -> Option<Shape> {
+        Some(Shape::Lines("[a-zA-Z]+"))
+    }
+}
+
+fn part1(input: &str) -> Result<u32, ParseError> {
+    parse_compartments(input)?
         .into_iter()
-        .map(|it| duplicate_item(it).pipe(priority))
-        .sum()
+        .map(|it| duplicate_item(&it, "compartments of the rucksack").map(priority))
+        .sum::<Result<_, _>>()
+        .map_err(|e| e.locate(3, input))
 }
 
-fn part2(input: &str) -> u32 {
-    parse_group(input)
+fn part2(input: &str) -> Result<u32, ParseError> {
+    parse_group(input)?
         .into_iter()
-        .map(|it| duplicate_item(it).pipe(priority))
-        .sum()
+        .map(|it| duplicate_item(&it, "rucksacks of the group").map(priority))
+        .sum::<Result<_, _>>()
+        .map_err(|e| e.locate(3, input))
 }
 
-fn parse_compartments(input: &str) -> Vec<Vec<&str>> {
-    input
-        .lines()
+fn parse_compartments(input: &str) -> Result<Vec<Vec<&str>>, ParseError> {
+    rucksacks(input)?
+        .into_iter()
         .map(|line| {
+            if line.len() % 2 != 0 {
+                return Err(
+                    ParseError::new(line, "expected two compartments of equal size")
+                        .locate(3, input),
+                );
+            }
             let mid = line.len() / 2;
-            vec![&line[..mid], &line[mid..]]
+            Ok(vec![&line[..mid], &line[mid..]])
         })
         .collect()
 }
 
-fn parse_group(input: &str) -> Vec<Vec<&str>> {
+fn parse_group(input: &str) -> Result<Vec<Vec<&str>>, ParseError> {
+    let rucksacks = rucksacks(input)?;
+    let groups = rucksacks.chunks_exact(3);
+    if let Some(last) = groups.remainder().last() {
+        return Err(ParseError::missing(last, "groups of three rucksacks").locate(3, input));
+    }
+    Ok(groups.map(<[&str]>::to_vec).collect())
+}
+
+fn rucksacks(input: &str) -> Result<Vec<&str>, ParseError> {
     input
         .lines()
-        .array_chunks()
-        .map(|[l1, l2, l3]| vec![l1, l2, l3])
-        .collect()
+        .map(
+            |line| match line.char_indices().find(|(_, c)| !c.is_ascii_alphabetic()) {
+                Some((ix, c)) => Err(ParseError::new(
+                    &line[ix..ix + c.len_utf8()],
+                    "expected items a-z or A-Z",
+                )),
+                None => Ok(line),
+            },
+        )
+        .collect::<Result<_, _>>()
+        .map_err(|e| e.locate(3, input))
 }
 
-fn duplicate_item(parts: Vec<&str>) -> char {
-    let mut parts = parts.into_iter();
-    let mut duplicates = parts.next().unwrap().chars().collect::<HashSet<_>>();
-    for part in parts {
+/// The one item in all of the `parts`, which are `what` the error mentions if there isn't one.
+fn duplicate_item(parts: &[&str], what: &str) -> Result<char, ParseError> {
+    let first = parts.first().copied().unwrap_or_default();
+    let mut duplicates = first.chars().collect::<HashSet<_>>();
+    for part in parts.iter().skip(1) {
         let part_chars = part.chars().collect();
         duplicates = duplicates.intersection(&part_chars).copied().collect();
     }
-    assert_eq!(duplicates.len(), 1);
-    duplicates.into_iter().next().unwrap()
+    let mut duplicates = duplicates.into_iter();
+    match (duplicates.next(), duplicates.next()) {
+        (Some(item), None) => Ok(item),
+        _ => Err(ParseError::new(
+            first,
+            format_args!("expected exactly one item in all {what}"),
+        )),
+    }
 }
 
 fn priority(c: char) -> u32 {
@@ -60,7 +107,21 @@ fn priority(c: char) -> u32 {
     }
 }
 
+#[test]
+fn no_single_duplicate() {
+    let error = part1("abac\nabcd\n").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        r#"day 3, line 2, column 1: expected exactly one item in all compartments of the rucksack, found "ab""#
+    );
+    let error = part2("ab\nab\nab\n").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        r#"day 3, line 1, column 1: expected exactly one item in all rucksacks of the group, found "ab""#
+    );
+}
+
 #[test]
 fn day3() {
-    assert_eq!(solve(), (8123, 2620))
+    assert_eq!(solve().unwrap(), (8123, 2620))
 }
@@ -1,26 +1,41 @@
 use std::ops::RangeInclusive;
 
+use aoc_common::{parse, Answer, ParseError, Shape, Solution};
+use eyre::Result;
 use tracing::instrument;
 
 use crate::input;
 
-pub fn solve() -> (u32, u32) {
-    let input = input(4);
+pub fn solve() -> Result<(u32, u32)> {
+    let input = input(4)?;
+    let ranges = parse(&input)?;
 
-    (part1(&input), part2(&input))
+    Ok((part1(&ranges), part2(&ranges)))
 }
 
-fn part1(input: &str) -> u32 {
-    let ranges = parse(input);
-    ranges
-        .into_iter()
-        .filter(|(l, r)| fully_contains(l, r))
-        .count() as u32
+pub struct Day4;
+
+impl Solution for Day4 {
+    fn part1(&self, input: &str) -> Result<Answer> {
+        Ok(part1(&parse(input)?).into())
+    }
+    fn part2(&self, input: &str) -> Result<Answer> {
+        Ok(part2(&parse(input)?).into())
+    }
+    fn parse(&self, input: &str) -> Option<Result<()>> {
+        aoc_common::parsed(parse(input))
+    }
+    fn input_shape(&self) -> Option<Shape> {
+        Some(Shape::Lines(r"\d+-\d+,\d+-\d+"))
+    }
 }
 
-fn part2(input: &str) -> u32 {
-    let ranges = parse(input);
-    ranges.into_iter().filter(|(l, r)| overlaps(l, r)).count() as u32
+fn part1(ranges: &[(Range, Range)]) -> u32 {
+    ranges.iter().filter(|(l, r)| fully_contains(l, r)).count() as u32
+}
+
+fn part2(ranges: &[(Range, Range)]) -> u32 {
+    ranges.iter().filter(|(l, r)| overlaps(l, r)).count() as u32
 }
 
 fn fully_contains(left: &Range, right: &Range) -> bool {
@@ -38,33 +53,29 @@ fn overlaps(left: &Range, right: &Range) -> bool {
 
 type Range = RangeInclusive<i32>;
 
-fn parse(input: &str) -> Vec<(Range, Range)> {
-    fn parse_range(range: &str) -> Range {
-        let (from, to) = range.split_once('-').unwrap();
-        from.parse().unwrap()..=to.parse().unwrap()
+fn parse(input: &str) -> Result<Vec<(Range, Range)>, ParseError> {
+    fn parse_range(range: &str) -> Result<Range, ParseError> {
+        let (from, to) = parse::split_once(range, "-")?;
+        Ok(parse::number(from)?..=parse::number(to)?)
     }
     input
         .lines()
         .map(|l| {
-            let (l, r) = l.split_once(',').unwrap();
-            (parse_range(l), parse_range(r))
+            let (l, r) = parse::split_once(l, ",")?;
+            Ok((parse_range(l)?, parse_range(r)?))
         })
-        .collect()
+        .collect::<Result<_, _>>()
+        .map_err(|e: ParseError| e.locate(4, input))
 }
 
 #[test]
-fn example() {
-    crate::init();
-    let input = "2-4,6-8
-2-3,4-5
-5-7,7-9
-2-8,3-7
-6-6,4-6
-2-6,4-8";
-    assert_eq!(part2(input), 4);
+fn day4() {
+    assert_eq!(solve().unwrap(), (576, 905));
 }
 
 #[test]
-fn day4() {
-    assert_eq!(solve(), (576, 905));
+fn truncated() {
+    let error = parse("2-4,6-8\n2-3,4-").unwrap_err();
+    assert_eq!((error.line, error.column), (2, 7));
+    assert_eq!(error.text, "");
 }
@@ -1,26 +1,49 @@
 use std::collections::BTreeMap;
 
+use aoc_common::{parse, Answer, ParseError, Shape, Solution};
+use eyre::{eyre, Result};
 use itertools::Itertools;
 use regex::Regex;
 use tracing::debug;
 
 use crate::input;
 
-pub fn solve() -> (String, String) {
-    let input = input(5);
-    (part1(&input), part2(&input))
+pub fn solve() -> Result<(String, String)> {
+    let input = input(5)?;
+    Ok((part1(&input)?, part2(&input)?))
 }
 
-fn part1(input: &str) -> String {
-    let (mut stack, moves) = parse(input);
+pub struct Day5;
+
+impl Solution for Day5 {
+    fn part1(&self, input: &str) -> Result<Answer> {
+        Ok(part1(input)?.into())
+    }
+    fn part2(&self, input: &str) -> Result<Answer> {
+        Ok(part2(input)?.into())
+    }
+    fn parse(&self, input: &str) -> Option<Result<()>> {
+        aoc_common::parsed(parse(input))
+    }
+    fn input_shape(&self) -> Option<Shape> {
+        Some(Shape::Blocks(&[
+            // The drawing is padded with spaces.
+            Shape::Lines(r"[ \[\]A-Z0-9]+"),
+            Shape::Lines(r"move \d+ from \d+ to \d+"),
+        ]))
+    }
+}
+
+fn part1(input: &str) -> Result<String> {
+    let (mut stack, moves) = parse(input)?;
     for m in moves {
         stack.apply_single_move(m);
     }
     stack.top_of_the_stack()
 }
 
-fn part2(input: &str) -> String {
-    let (mut stack, moves) = parse(input);
+fn part2(input: &str) -> Result<String> {
+    let (mut stack, moves) = parse(input)?;
     for m in moves {
         stack.apply_batch_move(m);
     }
@@ -30,37 +53,47 @@ fn part2(input: &str) -> String {
 #[derive(Debug, PartialEq)]
 struct Crates(BTreeMap<usize, Vec<char>>);
 impl Crates {
+    /// Moves the crates one at a time, which reverses their order.
     fn apply_single_move(&mut self, m: Move) {
         let before = format!("{self:?}");
-        for _ in 0..m.amount {
-            let c = self.0.get_mut(&m.from).unwrap().pop().unwrap();
-            self.0.get_mut(&m.to).unwrap().push(c);
-        }
+        let mut crates = self.take(&m);
+        crates.reverse();
+        self.0.entry(m.to).or_default().extend(crates);
         debug!(?before, after = ?self);
     }
 
     fn apply_batch_move(&mut self, m: Move) {
         let before = format!("{self:?}");
-        let from = self.0.get_mut(&m.from).unwrap();
-        let crates: Vec<_> = from.drain((from.len() - m.amount)..).collect();
-        self.0.get_mut(&m.to).unwrap().extend(crates);
+        let crates = self.take(&m);
+        self.0.entry(m.to).or_default().extend(crates);
         debug!(?before, after = ?self);
     }
 
-    fn top_of_the_stack(&self) -> String {
-        let mut s = String::new();
-        for stack in self.0.values() {
-            s.push(*stack.last().unwrap());
-        }
-        s
+    /// The top `m.amount` crates of `m.from`, which [`parse_moves`] checked are there.
+    fn take(&mut self, m: &Move) -> Vec<char> {
+        let from = self.0.entry(m.from).or_default();
+        from.split_off(from.len().saturating_sub(m.amount))
+    }
+
+    fn top_of_the_stack(&self) -> Result<String> {
+        self.0
+            .iter()
+            .map(|(id, stack)| {
+                stack
+                    .last()
+                    .ok_or_else(|| eyre!("stack {id} is empty after the moves"))
+            })
+            .collect()
     }
 }
 
-fn parse(input: &str) -> (Crates, Vec<Move>) {
-    let separator_ix = input.find("\n\n").unwrap();
-    let (crates, moves) = input.split_at(separator_ix);
-    let moves = &moves[2..];
-    (parse_crates(crates), parse_moves(moves))
+fn parse(input: &str) -> Result<(Crates, Vec<Move>), ParseError> {
+    let (crates, moves) = input.split_once("\n\n").ok_or_else(|| {
+        ParseError::missing(input.trim_end(), "an empty line before the moves").locate(5, input)
+    })?;
+    let crates = parse_crates(crates);
+    let moves = parse_moves(moves, &crates).map_err(|e| e.locate(5, input))?;
+    Ok((crates, moves))
 }
 
 fn parse_crates(input: &str) -> Crates {
@@ -78,26 +111,52 @@ fn parse_crates(input: &str) -> Crates {
     Crates(result)
 }
 
+#[derive(Debug)]
 struct Move {
     amount: usize,
     from: usize,
     to: usize,
 }
-fn parse_moves(input: &str) -> Vec<Move> {
-    let re = Regex::new(r"move (\d+) from (\d+) to (\d+)").unwrap();
-
-    input
-        .lines()
-        .map(|line| {
-            let captures = re.captures(line).unwrap();
-            let get_num = |pos| captures.get(pos).unwrap().as_str().parse().unwrap();
-            Move {
-                amount: get_num(1),
-                from: get_num(2),
-                to: get_num(3),
+/// Fails on moves of more crates than their stack has at that point.
+fn parse_moves(input: &str, crates: &Crates) -> Result<Vec<Move>, ParseError> {
+    let re = Regex::new(r"^move (\d+) from (\d+) to (\d+)$").unwrap();
+    let mut heights: BTreeMap<usize, usize> = crates
+        .0
+        .iter()
+        .map(|(&id, stack)| (id, stack.len()))
+        .collect();
+
+    let mut moves = Vec::new();
+    for line in input.lines() {
+        let captures = re
+            .captures(line)
+            .ok_or_else(|| ParseError::new(line, "expected \"move N from N to N\""))?;
+        let get_stack = |pos| {
+            let stack = captures.get(pos).unwrap().as_str();
+            let id = parse::number(stack)?;
+            match heights.get(&id) {
+                Some(&height) => Ok((id, height)),
+                None => Err(ParseError::new(stack, "no such stack")),
             }
-        })
-        .collect()
+        };
+        let amount = captures.get(1).unwrap().as_str();
+        let m = Move {
+            amount: parse::number(amount)?,
+            from: get_stack(2)?.0,
+            to: get_stack(3)?.0,
+        };
+        let height = heights[&m.from];
+        if m.amount > height {
+            return Err(ParseError::new(
+                amount,
+                format_args!("stack {} has only {height} crates", m.from),
+            ));
+        }
+        *heights.entry(m.from).or_default() -= m.amount;
+        *heights.entry(m.to).or_default() += m.amount;
+        moves.push(m);
+    }
+    Ok(moves)
 }
 
 #[test]
@@ -120,7 +179,28 @@ fn parses() {
     )
 }
 
+#[test]
+fn bad_moves() {
+    let crates = "    [D]\n[N] [C]\n[Z] [M] [P]\n 1   2   3\n\n";
+    let error = parse(&format!("{crates}move 1 from 2 to 1\nmove 3 from 1 to 4\n")).unwrap_err();
+    assert_eq!((error.line, error.column), (7, 18));
+    assert_eq!(error.reason, "no such stack");
+
+    let error = parse(&format!("{crates}move 1 from 2 to 1\nmove 3 fro")).unwrap_err();
+    assert_eq!((error.line, error.column), (7, 1));
+    let error = parse(crates.trim_end()).unwrap_err();
+    assert_eq!((error.line, error.column), (4, 11));
+
+    let error = parse(&format!("{crates}move 2 from 1 to 3\nmove 4 from 3 to 2\n")).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        r#"day 5, line 7, column 6: stack 3 has only 3 crates, found "4""#
+    );
+    let error = part1(&format!("{crates}move 1 from 3 to 1\n")).unwrap_err();
+    assert_eq!(error.to_string(), "stack 3 is empty after the moves");
+}
+
 #[test]
 fn day5() {
-    assert_eq!(solve(), ("LJSVLTWQM".into(), "BRQWDBBJM".into()))
+    assert_eq!(solve().unwrap(), ("LJSVLTWQM".into(), "BRQWDBBJM".into()))
 }
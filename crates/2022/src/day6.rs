@@ -1,31 +1,56 @@
 use std::collections::HashSet;
 
+use aoc_common::{Answer, Shape, Solution};
+use eyre::{eyre, Result};
+
 use crate::input;
 
-pub fn solve() -> (usize, usize) {
-    let input = input(6);
-    (part1(&input), part2(&input))
+pub fn solve() -> Result<(usize, usize)> {
+    let input = input(6)?;
+    Ok((part1(&input)?, part2(&input)?))
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    fn part1(&self, input: &str) -> eyre::Result<Answer> {
+        Ok(part1(input)?.into())
+    }
+    fn part2(&self, input: &str) -> eyre::Result<Answer> {
+        Ok(part2(input)?.into())
+    }
+    fn input_shape(&self) -> Option<Shape> {
+        Some(Shape::Line("[a-z]+"))
+    }
 }
 
-fn part1(input: &str) -> usize {
+fn part1(input: &str) -> Result<usize> {
     distinct_window_pos(input.as_bytes(), 4)
 }
 
-fn part2(input: &str) -> usize {
+fn part2(input: &str) -> Result<usize> {
     distinct_window_pos(input.as_bytes(), 14)
 }
 
-fn distinct_window_pos(s: &[u8], window_size: usize) -> usize {
-    let indexed = s.iter().enumerate().collect::<Vec<_>>();
-    let distinct_window = indexed
+fn distinct_window_pos(s: &[u8], window_size: usize) -> Result<usize> {
+    let start = s
         .windows(window_size)
-        .find(|window| window.iter().map(|(_, x)| x).collect::<HashSet<_>>().len() == window_size)
-        .unwrap();
+        .position(|window| window.iter().collect::<HashSet<_>>().len() == window_size)
+        .ok_or_else(|| eyre!("no {window_size} different characters in a row"))?;
     // 1-indexed
-    distinct_window.last().unwrap().0 + 1
+    Ok(start + window_size)
+}
+
+#[test]
+fn no_marker() {
+    assert_eq!(part1("aabcd").unwrap(), 5);
+    assert_eq!(
+        part1("abab").unwrap_err().to_string(),
+        "no 4 different characters in a row"
+    );
 }
 
 #[test]
 fn day6() {
-    assert_eq!(solve(), (1816, 2625))
+    assert_eq!(solve().unwrap(), (1816, 2625))
 }
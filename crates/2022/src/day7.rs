@@ -1,5 +1,7 @@
 use std::{collections::BTreeMap, iter};
 
+use aoc_common::{parse, Answer, ParseError, Shape, Solution};
+use eyre::Result;
 use tracing::debug;
 
 use crate::input;
@@ -7,10 +9,27 @@ use crate::input;
 const TOTAL_FS_SIZE: usize = 70000000;
 const FREE_SPACE_REQUIRED: usize = 30000000;
 
-pub fn solve() -> (usize, usize) {
-    let input = input(7);
-    let fs = parse(&input);
-    (part1(&fs), part2(&fs))
+pub fn solve() -> Result<(usize, usize)> {
+    let input = input(7)?;
+    let fs = parse(&input)?;
+    Ok((part1(&fs), part2(&fs)))
+}
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    fn part1(&self, input: &str) -> Result<Answer> {
+        Ok(part1(&parse(input)?).into())
+    }
+    fn part2(&self, input: &str) -> Result<Answer> {
+        Ok(part2(&parse(input)?).into())
+    }
+    fn parse(&self, input: &str) -> Option<Result<()>> {
+        aoc_common::parsed(parse(input))
+    }
+    fn input_shape(&self) -> Option<Shape> {
+        Some(Shape::Lines(r"\$ cd \S+|\$ ls|dir \S+|\d+ \S+"))
+    }
 }
 
 fn part1(fs: &Fs) -> usize {
@@ -35,29 +54,34 @@ fn part2(fs: &Fs) -> usize {
         .unwrap()
 }
 
-fn parse(input: &str) -> Fs {
+fn parse(input: &str) -> Result<Fs, ParseError> {
     let mut fs = Fs::new();
     for line in input.lines() {
         let mut words = line.split_ascii_whitespace();
-        let mut next = || words.next().unwrap();
-        match next() {
-            "$" => match next() {
-                "cd" => fs.cd(next().to_string()),
+        let mut next = |what| {
+            words
+                .next()
+                .ok_or_else(|| ParseError::missing(line, what).locate(7, input))
+        };
+        match next("a command or directory listing")? {
+            "$" => match next("a command")? {
+                "cd" => fs.cd(next("a directory")?.to_string()),
                 "ls" => continue,
-                other => panic!("Command {other:?} not supported"),
+                other => {
+                    return Err(ParseError::new(other, "expected cd or ls").locate(7, input));
+                }
             },
-            "dir" => fs.add_dir(next().to_string()),
+            "dir" => fs.add_dir(next("a directory name")?.to_string()),
             num => {
-                let num = num
-                    .parse::<usize>()
-                    .unwrap_or_else(|e| panic!("Couldn't parse file size in {line:?}: {e}"));
-                fs.add_file(num, next().to_string());
+                let num = parse::number(num).map_err(|e| e.locate(7, input))?;
+                fs.add_file(num, next("a file name")?.to_string());
             }
         }
     }
-    fs
+    Ok(fs)
 }
 
+#[derive(Debug)]
 struct Fs {
     root: Dir,
     cwd: Path,
@@ -154,5 +178,19 @@ type Path = Vec<String>;
 
 #[test]
 fn day7() {
-    assert_eq!(solve(), (1086293, 366028))
+    assert_eq!(solve().unwrap(), (1086293, 366028))
+}
+
+#[test]
+fn bad_commands() {
+    let error = parse("$ cd /\n$ ls\ndir a\n$ rm a\n").unwrap_err();
+    assert_eq!(
+        (error.line, error.column, error.text.as_str()),
+        (4, 3, "rm")
+    );
+    let error = parse("$ cd /\n$ ls\n14848514\n").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "day 7, line 3, column 9: expected a file name, found nothing"
+    );
 }
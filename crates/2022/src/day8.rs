@@ -1,31 +1,49 @@
+use aoc_common::{Answer, Coord, Grid, ParseError, Shape, Solution};
+use eyre::Result;
 use tap::Tap;
 use tracing::debug;
 
 use crate::input;
 
-pub fn solve() -> (usize, usize) {
-    let input = input(8);
-    (part1(&input), part2(&input))
+pub fn solve() -> Result<(usize, usize)> {
+    let input = input(8)?;
+    Ok((part1(&input)?, part2(&input)?))
 }
 
-type Coord = (usize, usize);
+pub struct Day8;
 
-fn part1(input: &str) -> usize {
-    let grid = Grid::parse(input);
+impl Solution for Day8 {
+    fn part1(&self, input: &str) -> Result<Answer> {
+        Ok(part1(input)?.into())
+    }
+    fn part2(&self, input: &str) -> Result<Answer> {
+        Ok(part2(input)?.into())
+    }
+    fn input_shape(&self) -> Option<Shape> {
+        Some(Shape::Grid {
+            cell: "[0-9]",
+            size: None,
+        })
+    }
+}
+
+const DIRECTIONS: [(&str, Coord); 4] = [
+    ("up", Coord::UP),
+    ("down", Coord::DOWN),
+    ("left", Coord::LEFT),
+    ("right", Coord::RIGHT),
+];
+
+fn part1(input: &str) -> Result<usize, ParseError> {
+    let forest = Forest::parse(input)?;
     let mut visible = 0;
-    for tree_coord in grid.all_tree_coords() {
-        let tree = grid.tree_at(tree_coord).unwrap();
+    for (tree_coord, &tree) in forest.0.iter() {
         debug!(?tree_coord, ?tree, "testing");
-        if [
-            ("up", up(tree_coord)),
-            ("down", down(tree_coord)),
-            ("left", left(tree_coord)),
-            ("right", right(tree_coord)),
-        ]
-        .into_iter()
-        .any(|(dir, line)| {
-            line.take_while(|coord| grid.tree_at(*coord).is_some())
-                .all(|coord| grid.tree_at(coord).unwrap() < tree)
+        if DIRECTIONS.into_iter().any(|(dir, step)| {
+            forest
+                .0
+                .ray(tree_coord, step)
+                .all(|coord| forest.0[coord] < tree)
                 .tap(|visible| {
                     if *visible {
                         debug!(from = ?dir, ?tree_coord, ?tree, "visible");
@@ -35,55 +53,44 @@ fn part1(input: &str) -> usize {
             visible += 1;
         }
     }
-    visible
+    Ok(visible)
 }
 
-fn part2(input: &str) -> usize {
-    let grid = Grid::parse(input);
-    grid.all_tree_coords()
-        .map(|coord| grid.scenic_score(coord))
+fn part2(input: &str) -> Result<usize, ParseError> {
+    let forest = Forest::parse(input)?;
+    Ok(forest
+        .0
+        .coords()
+        .map(|coord| forest.scenic_score(coord))
         .max()
-        .unwrap()
+        .unwrap_or(0))
 }
 
-struct Grid(Vec<Vec<char>>);
+struct Forest(Grid<char>);
 
-impl Grid {
-    fn parse(input: &str) -> Self {
-        Grid(input.lines().map(|l| l.chars().collect()).collect())
-    }
-    fn all_tree_coords(&self) -> impl Iterator<Item = Coord> + '_ {
-        (0..self.0.len()).flat_map(move |x| (0..self.0[x].len()).map(move |y| (x, y)))
-    }
-    fn tree_at(&self, coord: Coord) -> Option<char> {
-        let (x, y) = coord;
-        self.0
-            .get(x)
-            .and_then(|row: &Vec<char>| row.get(y))
-            .copied()
+impl Forest {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        Grid::parse(input, |c| c.is_ascii_digit().then_some(c))
+            .map(Forest)
+            .map_err(|e| e.locate(8, input))
     }
     fn scenic_score(&self, coord: Coord) -> usize {
-        [
-            ("up", up(coord)),
-            ("down", down(coord)),
-            ("left", left(coord)),
-            ("right", right(coord)),
-        ]
-        .into_iter()
-        .map(|(dir, line)| {
-            self.viewing_distance(coord, line)
-                .tap(|dist| debug!(?coord, ?dir, ?dist))
-        })
-        .product::<usize>()
-        .tap(|score| debug!(?coord, ?score))
+        DIRECTIONS
+            .into_iter()
+            .map(|(dir, step)| {
+                self.viewing_distance(coord, step)
+                    .tap(|dist| debug!(?coord, ?dir, ?dist))
+            })
+            .product::<usize>()
+            .tap(|score| debug!(?coord, ?score))
     }
 
-    fn viewing_distance(&self, coord: Coord, line: Box<dyn Iterator<Item = Coord>>) -> usize {
-        let tree = self.tree_at(coord).unwrap();
+    fn viewing_distance(&self, coord: Coord, step: Coord) -> usize {
+        let tree = self.0[coord];
         let mut dist = 0;
-        for next_coord in line.take_while(|coord| self.tree_at(*coord).is_some()) {
+        for next_coord in self.0.ray(coord, step) {
             dist += 1;
-            if self.tree_at(next_coord).unwrap() >= tree {
+            if self.0[next_coord] >= tree {
                 break;
             }
         }
@@ -91,36 +98,7 @@ impl Grid {
     }
 }
 
-fn up((x, y): Coord) -> Box<dyn Iterator<Item = Coord>> {
-    Box::new((0..x).rev().map(move |x| (x, y)))
-}
-
-fn down((x, y): Coord) -> Box<dyn Iterator<Item = Coord>> {
-    Box::new((x + 1..).map(move |x| (x, y)))
-}
-
-fn left((x, y): Coord) -> Box<dyn Iterator<Item = Coord>> {
-    Box::new((0..y).rev().map(move |y| (x, y)))
-}
-
-fn right((x, y): Coord) -> Box<dyn Iterator<Item = Coord>> {
-    Box::new((y + 1..).map(move |y| (x, y)))
-}
-
-#[test]
-fn example() {
-    crate::init();
-    let input = "\
-30373
-25512
-65332
-33549
-35390";
-    assert_eq!(part1(input), 21);
-    assert_eq!(part2(input), 8);
-}
-
 #[test]
 fn day8() {
-    assert_eq!(solve(), (1794, 199272))
+    assert_eq!(solve().unwrap(), (1794, 199272))
 }
@@ -1,16 +1,35 @@
 use std::{collections::HashSet, ops::AddAssign, str::FromStr};
 
+use aoc_common::{parse, Answer, ParseError, Shape, Solution};
+use eyre::Result;
 use tracing::instrument;
 
 use crate::input;
 
-pub fn solve() -> (usize, usize) {
-    let input = input(9);
-    (part1(&input), part2(&input))
+pub fn solve() -> Result<(usize, usize)> {
+    let input = input(9)?;
+    let moves = parse(&input)?;
+    Ok((part1(&moves), part2(&moves)))
 }
 
-fn part1(input: &str) -> usize {
-    let moves = parse(input);
+pub struct Day9;
+
+impl Solution for Day9 {
+    fn part1(&self, input: &str) -> Result<Answer> {
+        Ok(part1(&parse(input)?).into())
+    }
+    fn part2(&self, input: &str) -> Result<Answer> {
+        Ok(part2(&parse(input)?).into())
+    }
+    fn parse(&self, input: &str) -> Option<Result<()>> {
+        aoc_common::parsed(parse(input))
+    }
+    fn input_shape(&self) -> Option<Shape> {
+        Some(Shape::Lines(r"[LRUD] \d+"))
+    }
+}
+
+fn part1(moves: &[Move]) -> usize {
     let mut state = State::new(2);
     for m in moves {
         state.apply(m);
@@ -18,8 +37,7 @@ fn part1(input: &str) -> usize {
     state.visited.len()
 }
 
-fn part2(input: &str) -> usize {
-    let moves = parse(input);
+fn part2(moves: &[Move]) -> usize {
     let mut state = State::new(10);
     for m in moves {
         state.apply(m);
@@ -27,12 +45,8 @@ fn part2(input: &str) -> usize {
     state.visited.len()
 }
 
-fn parse(input: &str) -> Vec<Move> {
-    input
-        .lines()
-        .map(Move::from_str)
-        .collect::<Result<Vec<_>, _>>()
-        .unwrap()
+fn parse(input: &str) -> Result<Vec<Move>, ParseError> {
+    parse::lines(9, input)
 }
 
 #[derive(Default, PartialEq, Eq, Hash, Clone, Copy, Debug)]
@@ -117,7 +131,7 @@ impl State {
         visited.insert(rope.tail());
         Self { rope, visited }
     }
-    fn apply(&mut self, m: Move) {
+    fn apply(&mut self, m: &Move) {
         for _ in 0..m.amount {
             self.rope.apply(m.dir);
             self.visited.insert(self.rope.tail());
@@ -131,13 +145,13 @@ struct Move {
     amount: usize,
 }
 impl FromStr for Move {
-    type Err = !;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (dir, amount) = s.split_once(' ').unwrap();
+        let (dir, amount) = parse::split_once(s, " ")?;
         Ok(Move {
-            dir: dir.parse().unwrap(),
-            amount: amount.parse().unwrap(),
+            dir: dir.parse()?,
+            amount: parse::number(amount)?,
         })
     }
 }
@@ -151,7 +165,7 @@ enum Dir {
 }
 
 impl FromStr for Dir {
-    type Err = !;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         use Dir::*;
@@ -160,26 +174,11 @@ impl FromStr for Dir {
             "R" => R,
             "U" => U,
             "D" => D,
-            _ => panic!("Invalid Dir {s:?}"),
+            _ => return Err(ParseError::new(s, "expected L, R, U or D")),
         })
     }
 }
 
-#[test]
-fn example() {
-    crate::init();
-    let input = "R 4
-U 4
-L 3
-D 1
-R 4
-D 1
-L 5
-R 2
-";
-    assert_eq!(part1(input), 13);
-}
-
 #[test]
 fn follow_diagonally() {
     crate::init();
@@ -187,7 +186,18 @@ fn follow_diagonally() {
     assert_eq!(from.follow(Coord { x: 2, y: 1 }), Coord { x: 1, y: 1 });
 }
 
+#[test]
+fn bad_moves() {
+    let error = parse("R 4\nU 4\nX 3\n").unwrap_err();
+    assert_eq!((error.line, error.column, error.text.as_str()), (3, 1, "X"));
+    let error = parse("R 4\nU -4\n").unwrap_err();
+    assert_eq!(
+        (error.line, error.column, error.text.as_str()),
+        (2, 3, "-4")
+    );
+}
+
 #[test]
 fn day9() {
-    assert_eq!(solve(), (6256, 2665))
+    assert_eq!(solve().unwrap(), (6256, 2665))
 }
@@ -1,7 +1,6 @@
-#![feature(never_type)]
-#![feature(iter_array_chunks)]
+use std::env;
 
-use std::{env, fs};
+use aoc_common::{InputError, Puzzle};
 
 pub mod day1;
 pub mod day10;
@@ -16,8 +15,23 @@ pub mod day7;
 pub mod day8;
 pub mod day9;
 
-pub fn input(day: u32) -> String {
-    fs::read_to_string(format!("inputs/input{day}.csv")).unwrap()
+pub const PUZZLES: &[Puzzle] = &[
+    Puzzle::new(2022, 1, &day1::Day1),
+    Puzzle::new(2022, 2, &day2::Day2),
+    Puzzle::new(2022, 3, &day3::Day3),
+    Puzzle::new(2022, 4, &day4::Day4),
+    Puzzle::new(2022, 5, &day5::Day5),
+    Puzzle::new(2022, 6, &day6::Day6),
+    Puzzle::new(2022, 7, &day7::Day7),
+    Puzzle::new(2022, 8, &day8::Day8),
+    Puzzle::new(2022, 9, &day9::Day9),
+    Puzzle::new(2022, 10, &day10::Day10),
+    Puzzle::new(2022, 11, &day11::Day11),
+    Puzzle::new(2022, 12, &day12::Day12),
+];
+
+pub fn input(day: u32) -> Result<String, InputError> {
+    aoc_common::read_input(2022, day)
 }
 
 pub fn init() {
@@ -1,11 +1,27 @@
+use aoc_common::{Answer, Shape, Solution};
+
 use crate::input;
 
-pub fn solve() -> (u64, u64) {
-    let input = input(1);
+pub fn solve() -> eyre::Result<(u64, u64)> {
+    let input = input(1)?;
     let part1 = find_digits(&input, translate_digit);
     let part2 = find_digits(&input, translate_both);
 
-    (part1, part2)
+    Ok((part1, part2))
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    fn part1(&self, input: &str) -> eyre::Result<Answer> {
+        Ok(find_digits(input, translate_digit).into())
+    }
+    fn part2(&self, input: &str) -> eyre::Result<Answer> {
+        Ok(find_digits(input, translate_both).into())
+    }
+    fn input_shape(&self) -> Option<Shape> {
+        Some(Shape::Lines("[a-z0-9]+"))
+    }
 }
 
 fn find_digits(input: &str, find_digit_fn: impl Fn(&str) -> Option<u8>) -> u64 {
@@ -67,22 +83,6 @@ sevenine",
     #[test]
     fn day1() {
         tracing_subscriber::fmt::try_init().ok();
-        assert_eq!(solve(), (55002, 55093))
-    }
-
-    #[test]
-    fn example() {
-        tracing_subscriber::fmt::try_init().ok();
-        let result = find_digits(
-            "two1nine
-eightwothree
-abcone2threexyz
-xtwone3four
-4nineeightseven2
-zoneight234
-7pqrstsixteen",
-            translate_both,
-        );
-        assert_eq!(result, 281);
+        assert_eq!(solve().unwrap(), (55002, 55093))
     }
 }
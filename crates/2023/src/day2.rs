@@ -1,25 +1,43 @@
 use std::str::FromStr;
 
+use aoc_common::{Answer, Shape, Solution};
 use eyre::{bail, eyre, Result};
 
 use crate::input;
 
-pub fn solve() -> (u64, u64) {
-    let input = input(2);
-    let games = input
-        .lines()
-        .map(Game::from_str)
-        .collect::<Result<Vec<_>>>()
-        .unwrap();
-    let part1 = part1(
-        &games,
-        &Colors {
-            red: 12,
-            green: 13,
-            blue: 14,
-        },
-    );
-    (part1, part2(&games))
+pub fn solve() -> eyre::Result<(u64, u64)> {
+    let input = input(2)?;
+    let games = parse(&input)?;
+    Ok((part1(&games, &BAG), part2(&games)))
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    fn part1(&self, input: &str) -> Result<Answer> {
+        Ok(part1(&parse(input)?, &BAG).into())
+    }
+    fn part2(&self, input: &str) -> Result<Answer> {
+        Ok(part2(&parse(input)?).into())
+    }
+    fn parse(&self, input: &str) -> Option<Result<()>> {
+        aoc_common::parsed(parse(input))
+    }
+    fn input_shape(&self) -> Option<Shape> {
+        Some(Shape::Lines(
+            r"Game \d+: \d+ (red|green|blue)((, |; )\d+ (red|green|blue))*",
+        ))
+    }
+}
+
+const BAG: Colors = Colors {
+    red: 12,
+    green: 13,
+    blue: 14,
+};
+
+fn parse(input: &str) -> Result<Vec<Game>> {
+    input.lines().map(Game::from_str).collect()
 }
 
 fn part1(games: &[Game], constraint: &Colors) -> u64 {
@@ -96,7 +114,7 @@ impl FromStr for Colors {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut draws = Colors::default();
         for draw in s.trim().split(',') {
-            let next_draw: Vec<_> = draw.trim().split_whitespace().collect();
+            let next_draw: Vec<_> = draw.split_whitespace().collect();
             assert_eq!(next_draw.len(), 2);
             let num: u32 = next_draw[0].parse()?;
             match next_draw[1] {
@@ -116,6 +134,6 @@ mod tests {
 
     #[test]
     fn day2() {
-        assert_eq!(solve(), (2683, 49710))
+        assert_eq!(solve().unwrap(), (2683, 49710))
     }
 }
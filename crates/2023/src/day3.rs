@@ -1,29 +1,51 @@
+use aoc_common::{Answer, Coord, Shape, Solution};
+
 use crate::input;
 
-pub fn solve() -> (u64, u64) {
-    let input = input(3);
+pub fn solve() -> eyre::Result<(u64, u64)> {
+    let input = input(3)?;
     let schematic = parse(&input);
-    (
+    Ok((
         schematic.numbers_next_to_symbols().sum(),
         schematic.gear_ratios().sum(),
-    )
+    ))
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    fn part1(&self, input: &str) -> eyre::Result<Answer> {
+        Ok(parse(input).numbers_next_to_symbols().sum::<u64>().into())
+    }
+    fn part2(&self, input: &str) -> eyre::Result<Answer> {
+        Ok(parse(input).gear_ratios().sum::<u64>().into())
+    }
+    fn parse(&self, input: &str) -> Option<eyre::Result<()>> {
+        aoc_common::parsed(eyre::Ok(parse(input)))
+    }
+    fn input_shape(&self) -> Option<Shape> {
+        Some(Shape::Grid {
+            cell: r"[^\s]",
+            size: None,
+        })
+    }
 }
 
 fn parse(input: &str) -> Schematic {
     let mut numbers = Vec::new();
     let mut symbols = Vec::new();
-    'line: for (x, line) in input.lines().enumerate() {
-        let mut indexed = line.chars().enumerate();
+    'line: for (y, line) in (0..).zip(input.lines()) {
+        let mut indexed = (0..).zip(line.chars());
         loop {
             match indexed.next() {
-                Some((y, c)) if c.is_digit(10) => {
-                    let mut coords = vec![Coord { x, y }];
+                Some((x, c)) if c.is_ascii_digit() => {
+                    let mut coords = vec![Coord::new(x, y)];
                     let mut number = c.to_string();
-                    for (y, c) in indexed.clone().take_while(|(_, c)| c.is_digit(10)) {
+                    for (x, c) in indexed.clone().take_while(|(_, c)| c.is_ascii_digit()) {
                         // advance the main line iterator
                         indexed.next();
 
-                        coords.push(Coord { x, y });
+                        coords.push(Coord::new(x, y));
                         number.push(c);
                     }
                     numbers.push(Number {
@@ -32,7 +54,7 @@ fn parse(input: &str) -> Schematic {
                     });
                 }
                 Some((_, '.')) => {}
-                Some((y, _)) => symbols.push(Coord { x, y }),
+                Some((x, _)) => symbols.push(Coord::new(x, y)),
                 None => continue 'line,
             }
         }
@@ -53,8 +75,8 @@ impl Schematic {
             .filter(|num| {
                 let mut matching_symbol = None;
                 let result = self.symbols.iter().any(|sym| {
-                    sym.neighbors().iter().any(|coord| {
-                        let matches = num.coords.contains(coord);
+                    sym.neighbors8().any(|coord| {
+                        let matches = num.coords.contains(&coord);
                         if matches {
                             matching_symbol = Some(sym);
                         }
@@ -77,8 +99,8 @@ impl Schematic {
                 .numbers
                 .iter()
                 .filter(|num| {
-                    sym.neighbors().iter().any(|coord| {
-                        let matches = num.coords.contains(coord);
+                    sym.neighbors8().any(|coord| {
+                        let matches = num.coords.contains(&coord);
                         if matches {
                             // matching_symbol = Some(sym);
                         }
@@ -101,62 +123,12 @@ struct Number {
     coords: Vec<Coord>,
 }
 
-#[derive(PartialEq, Clone)]
-struct Coord {
-    x: usize,
-    y: usize,
-}
-
-impl Coord {
-    fn neighbors(&self) -> Vec<Coord> {
-        let Coord { x, y } = self;
-        let mut neighbors = Vec::new();
-        for x in [x - 1, *x, x + 1] {
-            for y in [y - 1, *y, y + 1] {
-                if x == self.x && y == self.y {
-                    continue;
-                }
-                neighbors.push(Coord { x, y });
-            }
-        }
-        neighbors
-    }
-}
-
-impl std::fmt::Debug for Coord {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "({},{})", self.x, self.y)
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn day3() {
-        assert_eq!(solve(), (554003, 87263515))
-    }
-
-    #[test]
-    fn example_pt1() {
-        tracing_subscriber::fmt::try_init().ok();
-        let input = "
-467..114..
-...*......
-..35..633.
-......#...
-617*......
-.....+.58.
-..592.....
-......755.
-...$.*....
-.664.598..
-"
-        .trim();
-        let schematic = parse(input);
-        tracing::info!(?schematic);
-
-        assert_eq!(schematic.numbers_next_to_symbols().sum::<u64>(), 4361);
+        assert_eq!(solve().unwrap(), (554003, 87263515))
     }
 }
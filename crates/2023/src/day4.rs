@@ -1,13 +1,31 @@
 use std::str::FromStr;
 
+use aoc_common::{Answer, Shape, Solution};
 use eyre::{eyre, Result};
 
 use crate::input;
 
-pub fn solve() -> (u64, u64) {
-    let input = input(4);
+pub fn solve() -> eyre::Result<(u64, u64)> {
+    let input = input(4)?;
     let cards = parse(&input);
-    (part1(&cards), part2(&cards))
+    Ok((part1(&cards), part2(&cards)))
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    fn part1(&self, input: &str) -> Result<Answer> {
+        Ok(part1(&parse(input)).into())
+    }
+    fn part2(&self, input: &str) -> Result<Answer> {
+        Ok(part2(&parse(input)).into())
+    }
+    fn parse(&self, input: &str) -> Option<Result<()>> {
+        aoc_common::parsed(eyre::Ok(parse(input)))
+    }
+    fn input_shape(&self) -> Option<Shape> {
+        Some(Shape::Lines(r"Card +\d+:( +\d+)+ \|( +\d+)+"))
+    }
 }
 
 fn part1(cards: &[Card]) -> u64 {
@@ -77,8 +95,7 @@ impl FromStr for Card {
         })?;
         let (winning, have) = rest.split_once('|').ok_or(eyre!("no |"))?;
         let parse_numbers = |s: &str| {
-            s.trim()
-                .split_whitespace()
+            s.split_whitespace()
                 .map(|n| n.parse::<u64>().map_err(Into::into))
                 .collect::<Result<Vec<_>>>()
         };
@@ -99,24 +116,6 @@ mod tests {
 
     #[test]
     fn day4() {
-        assert_eq!(solve(), (20829, 12648035))
-    }
-
-    #[test]
-    fn example() {
-        tracing_subscriber::fmt::try_init().ok();
-        let input = "
-Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
-Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
-Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
-Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
-Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
-Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11
-"
-        .trim();
-        let cards = parse(input);
-        let part1 = part1(&cards);
-        assert_eq!(part1, 13);
-        assert_eq!(part2(&cards), 30);
+        assert_eq!(solve().unwrap(), (20829, 12648035))
     }
 }
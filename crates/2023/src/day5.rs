@@ -1,17 +1,39 @@
 use std::{collections::HashMap, str::FromStr};
 
+use aoc_common::{Answer, Shape, Solution};
 use eyre::{bail, eyre, Result};
-use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+use tracing::{debug, debug_span, instrument};
 
 use crate::input;
 
-pub fn solve() -> (u64, u64) {
-    let input = input(5);
-    let almanac: Almanac = input.parse().unwrap();
+pub fn solve() -> eyre::Result<(u64, u64)> {
+    let input = input(5)?;
+    let almanac: Almanac = input.parse()?;
 
-    (part1(&almanac), part2(&almanac))
+    Ok((part1(&almanac), part2(&almanac)))
 }
 
+pub struct Day5;
+
+impl Solution for Day5 {
+    fn part1(&self, input: &str) -> Result<Answer> {
+        let almanac: Almanac = input.parse()?;
+        Ok(part1(&almanac).into())
+    }
+    fn part2(&self, input: &str) -> Result<Answer> {
+        let almanac: Almanac = input.parse()?;
+        Ok(part2(&almanac).into())
+    }
+    fn input_shape(&self) -> Option<Shape> {
+        Some(Shape::Blocks(&[
+            Shape::Line(r"seeds:( \d+)+"),
+            Shape::Lines(r"[a-z]+-to-[a-z]+ map:|\d+ \d+ \d+"),
+        ]))
+    }
+}
+
+#[instrument(level = "debug", skip_all)]
 fn part1(almanac: &Almanac) -> u64 {
     almanac
         .seeds
@@ -22,17 +44,22 @@ fn part1(almanac: &Almanac) -> u64 {
         .unwrap()
 }
 
+#[instrument(level = "debug", skip_all)]
 fn part2(almanac: &Almanac) -> u64 {
-    let mut seeds = Vec::new();
-    for range in almanac.seeds.chunks_exact(2) {
-        let start = range[0];
-        let length = range[1];
-        seeds.extend(start..(start + length));
-    }
-    seeds
-        .par_iter()
-        .copied()
-        .map(|seed| almanac.location(seed))
+    almanac
+        .seeds
+        .chunks_exact(2)
+        .map(|range| {
+            let (start, length) = (range[0], range[1]);
+            let _span = debug_span!("seed_range", start, length).entered();
+            let lowest = (start..(start + length))
+                .into_par_iter()
+                .map(|seed| almanac.location(seed))
+                .min()
+                .unwrap();
+            debug!(lowest);
+            lowest
+        })
         .min()
         .unwrap()
 }
@@ -54,7 +81,10 @@ impl Almanac {
         }
     }
     fn lookup(&self, thing: &str, num: u64) -> (u64, String) {
-        let map = &self.maps.get(thing).expect(&format!("no map for {thing}"));
+        let map = &self
+            .maps
+            .get(thing)
+            .unwrap_or_else(|| panic!("no map for {thing}"));
         let result_num = map.convert(num);
         (result_num, map.destination.clone())
     }
@@ -63,6 +93,7 @@ impl Almanac {
 impl FromStr for Almanac {
     type Err = eyre::Error;
 
+    #[instrument(name = "parse", level = "debug", skip_all)]
     fn from_str(s: &str) -> Result<Self> {
         let mut paragraphs = s.split("\n\n");
         let seeds = paragraphs.next().ok_or(eyre!("no seeds paragraph"))?;
@@ -110,7 +141,7 @@ impl FromStr for Map {
         let header = lines.next().ok_or(eyre!("no first line"))?;
         let mapping_string = header.trim_end_matches(" map:");
         let (source, destination) = mapping_string.split_once("-to-").ok_or(eyre!("no -to-"))?;
-        let ranges = lines.map(|line| Ok(line.parse()?)).collect::<Result<_>>()?;
+        let ranges = lines.map(|line| line.parse()).collect::<Result<_>>()?;
         Ok(Map {
             source: source.to_string(),
             destination: destination.to_string(),
@@ -0,0 +1,19 @@
+use aoc_common::{InputError, Puzzle};
+
+pub mod day1;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+
+pub const PUZZLES: &[Puzzle] = &[
+    Puzzle::new(2023, 1, &day1::Day1),
+    Puzzle::new(2023, 2, &day2::Day2),
+    Puzzle::new(2023, 3, &day3::Day3),
+    Puzzle::new(2023, 4, &day4::Day4),
+    Puzzle::new(2023, 5, &day5::Day5),
+];
+
+pub fn input(day: u32) -> Result<String, InputError> {
+    aoc_common::read_input(2023, day)
+}
@@ -1,9 +1,11 @@
 use std::collections::HashMap;
 
+use aoc_common::{Answer, Shape, Solution};
+
 use crate::input;
 
 pub fn solve() -> eyre::Result<(u64, u64)> {
-    let input = input(1);
+    let input = input(1)?;
     let lists = Lists::parse(&input)?;
     let part1 = part1(&lists)?;
     let part2 = part2(&lists);
@@ -13,12 +15,28 @@ pub fn solve() -> eyre::Result<(u64, u64)> {
     Ok((part1, part2))
 }
 
+pub struct Day1;
+
+impl Solution for Day1 {
+    fn part1(&self, input: &str) -> eyre::Result<Answer> {
+        let lists = Lists::parse(input)?;
+        Ok(part1(&lists)?.into())
+    }
+    fn part2(&self, input: &str) -> eyre::Result<Answer> {
+        let lists = Lists::parse(input)?;
+        Ok(part2(&lists).into())
+    }
+    fn input_shape(&self) -> Option<Shape> {
+        Some(Shape::Lines(r"\d+ +\d+"))
+    }
+}
+
 fn part1(lists: &Lists) -> eyre::Result<u64> {
     Ok(lists
         .left
         .iter()
         .zip(&lists.right)
-        .map(|(l, r)| ((l - r).abs()) as u64)
+        .map(|(l, r)| (l - r).unsigned_abs())
         .sum())
 }
 
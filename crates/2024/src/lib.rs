@@ -0,0 +1,9 @@
+use aoc_common::{InputError, Puzzle};
+
+pub mod day1;
+
+pub const PUZZLES: &[Puzzle] = &[Puzzle::new(2024, 1, &day1::Day1)];
+
+pub fn input(day: u32) -> Result<String, InputError> {
+    aoc_common::read_input(2024, day)
+}
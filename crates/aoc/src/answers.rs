@@ -0,0 +1,151 @@
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use eyre::{bail, Context, Result};
+use toml::{Table, Value};
+use toml_edit::DocumentMut;
+
+/// `answers/` in the workspace root.
+pub fn default_dir() -> PathBuf {
+    crate::workspace_root().join("answers")
+}
+
+/// Confirmed answers, one `{year}.toml` per year with a `[day{N}]` table holding `part1` and
+/// `part2`.
+pub struct Answers {
+    dir: PathBuf,
+    years: HashMap<u32, Table>,
+}
+
+impl Answers {
+    pub fn new(dir: PathBuf) -> Self {
+        Answers {
+            dir,
+            years: HashMap::new(),
+        }
+    }
+
+    pub fn get(&mut self, year: u32, day: u32, part: u32) -> Result<Option<String>> {
+        let table = match self.years.entry(year) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(load(&self.dir.join(format!("{year}.toml")))?),
+        };
+        let Some(value) = table
+            .get(&format!("day{day}"))
+            .and_then(|day| day.get(format!("part{part}")))
+        else {
+            return Ok(None);
+        };
+        match value {
+            Value::String(answer) => Ok(Some(answer.clone())),
+            Value::Integer(answer) => Ok(Some(answer.to_string())),
+            other => bail!("{year} day{day}.{part}: unexpected answer {other}"),
+        }
+    }
+
+    /// Stores a confirmed answer, keeping the comments and layout of the file.
+    pub fn record(&mut self, year: u32, day: u32, part: u32, answer: &str) -> Result<()> {
+        let path = self.dir.join(format!("{year}.toml"));
+        let (header, text) = match fs::read_to_string(&path) {
+            Ok(text) => (String::new(), text),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => (
+                format!("# Confirmed answers of {year}, checked by `aoc --verify`.\n\n"),
+                String::new(),
+            ),
+            Err(e) => return Err(e).wrap_err_with(|| format!("failed to read {}", path.display())),
+        };
+        let mut document: DocumentMut = text
+            .parse()
+            .wrap_err_with(|| format!("failed to parse {}", path.display()))?;
+        let Some(table) = document
+            .entry(&format!("day{day}"))
+            .or_insert(toml_edit::table())
+            .as_table_mut()
+        else {
+            bail!("{}: day{day} is not a table", path.display());
+        };
+        let answer = answer.trim_end();
+        table[&format!("part{part}")] = match answer.parse::<i64>() {
+            Ok(number) => toml_edit::value(number),
+            Err(_) => toml_edit::value(answer),
+        };
+        fs::create_dir_all(&self.dir)
+            .wrap_err_with(|| format!("failed to create {}", self.dir.display()))?;
+        fs::write(&path, header + &document.to_string())
+            .wrap_err_with(|| format!("failed to write {}", path.display()))?;
+        self.years.remove(&year);
+        Ok(())
+    }
+}
+
+fn load(path: &Path) -> Result<Table> {
+    match fs::read_to_string(path) {
+        Ok(text) => text
+            .parse()
+            .wrap_err_with(|| format!("failed to parse {}", path.display())),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Table::new()),
+        Err(e) => Err(e).wrap_err_with(|| format!("failed to read {}", path.display())),
+    }
+}
+
+/// Multi-line answers are stored without their trailing newline.
+pub fn matches(expected: &str, answer: &str) -> bool {
+    expected.trim_end() == answer.trim_end()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lookup() {
+        let dir = std::env::temp_dir().join(format!("aoc-answers-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("2022.toml"),
+            "[day5]\npart1 = \"LJSVLTWQM\"\n\n[day6]\npart1 = 1816\npart2 = \"\"\"\n#..\n.#.\"\"\"\n",
+        )
+        .unwrap();
+        let mut answers = Answers::new(dir.clone());
+
+        assert_eq!(answers.get(2022, 5, 1).unwrap().unwrap(), "LJSVLTWQM");
+        assert_eq!(answers.get(2022, 6, 1).unwrap().unwrap(), "1816");
+        assert!(matches(
+            &answers.get(2022, 6, 2).unwrap().unwrap(),
+            "#..\n.#.\n"
+        ));
+        assert_eq!(answers.get(2022, 5, 2).unwrap(), None);
+        assert_eq!(answers.get(2021, 1, 1).unwrap(), None);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn record() {
+        let dir = std::env::temp_dir().join(format!("aoc-answers-record-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("2022.toml"), "# 2022\n\n[day1]\npart1 = 74711\n").unwrap();
+        let mut answers = Answers::new(dir.clone());
+        assert_eq!(answers.get(2022, 1, 2).unwrap(), None);
+
+        answers.record(2022, 1, 2, "209481").unwrap();
+        answers.record(2022, 5, 1, "LJSVLTWQM").unwrap();
+        answers.record(2021, 1, 1, "1832\n").unwrap();
+
+        assert_eq!(answers.get(2022, 1, 2).unwrap().unwrap(), "209481");
+        assert_eq!(answers.get(2021, 1, 1).unwrap().unwrap(), "1832");
+        assert_eq!(
+            fs::read_to_string(dir.join("2022.toml")).unwrap(),
+            "# 2022\n\n[day1]\npart1 = 74711\npart2 = 209481\n\n[day5]\npart1 = \"LJSVLTWQM\"\n"
+        );
+        assert_eq!(
+            fs::read_to_string(dir.join("2021.toml")).unwrap(),
+            "# Confirmed answers of 2021, checked by `aoc --verify`.\n\n[day1]\npart1 = 1832\n"
+        );
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,130 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use eyre::{ensure, Context, Result};
+
+/// `examples/` in the workspace root.
+pub fn default_dir() -> PathBuf {
+    crate::workspace_root().join("examples")
+}
+
+/// An example from a puzzle description. Its input is `{dir}/{year}/day{day}/{name}.txt`, the
+/// expected answers are in `{name}.part1` and `{name}.part2` next to it. Parts without an
+/// answer file aren't checked, as some examples only apply to one part, and neither are parts
+/// whose answer file is still empty like `aoc new-day` creates them. Trailing whitespace in
+/// answer files is ignored.
+pub struct Example {
+    pub name: String,
+    pub input: String,
+    pub expected: [Option<String>; 2],
+}
+
+/// All examples of a day, ordered by name.
+pub fn load(dir: &Path, year: u32, day: u32) -> Result<Vec<Example>> {
+    let day_dir = dir.join(year.to_string()).join(format!("day{day}"));
+    let entries = match fs::read_dir(&day_dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e).wrap_err_with(|| format!("failed to read {}", day_dir.display())),
+    };
+    let mut inputs = Vec::new();
+    for entry in entries {
+        let path = entry?.path();
+        if path.extension().is_some_and(|ext| ext == "txt") {
+            inputs.push(path);
+        }
+    }
+    inputs.sort();
+
+    inputs
+        .into_iter()
+        .map(|path| {
+            let name = path
+                .file_stem()
+                .expect("inputs have a file name")
+                .to_string_lossy()
+                .into_owned();
+            let answer = |part: u32| read_optional(&path.with_extension(format!("part{part}")));
+            let answers = [answer(1)?, answer(2)?];
+            ensure!(
+                answers.iter().any(Option::is_some),
+                "{} has neither a `{name}.part1` nor a `{name}.part2` answer file",
+                path.display()
+            );
+            let expected = answers.map(|answer| answer.filter(|answer| !answer.is_empty()));
+            Ok(Example {
+                input: read(&path)?,
+                name,
+                expected,
+            })
+        })
+        .collect()
+}
+
+fn read(path: &Path) -> Result<String> {
+    fs::read_to_string(path).wrap_err_with(|| format!("failed to read {}", path.display()))
+}
+
+fn read_optional(path: &Path) -> Result<Option<String>> {
+    match fs::read_to_string(path) {
+        Ok(text) => Ok(Some(text.trim_end().to_string())),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e).wrap_err_with(|| format!("failed to read {}", path.display())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn discovery() {
+        let dir = std::env::temp_dir().join(format!("aoc-examples-{}", std::process::id()));
+        let day = dir.join("2022/day11");
+        fs::create_dir_all(&day).unwrap();
+        fs::write(day.join("larger.txt"), "2\n").unwrap();
+        fs::write(day.join("larger.part2"), "20\n").unwrap();
+        fs::write(day.join("example.txt"), "1\n").unwrap();
+        fs::write(day.join("example.part1"), "10\n").unwrap();
+        fs::write(day.join("example.part2"), "#.\n.#\n").unwrap();
+
+        let examples = load(&dir, 2022, 11).unwrap();
+        let names: Vec<&str> = examples.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, ["example", "larger"]);
+        assert_eq!(examples[0].input, "1\n");
+        assert_eq!(
+            examples[0].expected,
+            [Some("10".to_string()), Some("#.\n.#".to_string())]
+        );
+        assert_eq!(examples[1].expected, [None, Some("20".to_string())]);
+        assert!(load(&dir, 2022, 12).unwrap().is_empty());
+
+        fs::write(day.join("blank.txt"), "3\n").unwrap();
+        fs::write(day.join("blank.part1"), " \n").unwrap();
+        fs::write(day.join("blank.part2"), "").unwrap();
+        assert_eq!(load(&dir, 2022, 11).unwrap()[0].expected, [None, None]);
+
+        fs::write(day.join("unchecked.txt"), "3\n").unwrap();
+        assert!(load(&dir, 2022, 11).is_err());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn examples_have_declared_shapes() {
+        for puzzle in crate::registry::puzzles() {
+            let shape = puzzle.solution.input_shape();
+            for example in load(&default_dir(), puzzle.year, puzzle.day).unwrap() {
+                let errors = aoc_common::shape::check(puzzle.day, &example.input, shape.as_ref());
+                assert!(
+                    errors.is_empty(),
+                    "{puzzle:?} {}: {}",
+                    example.name,
+                    errors[0]
+                );
+            }
+        }
+    }
+}
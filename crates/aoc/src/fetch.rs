@@ -0,0 +1,202 @@
+use std::{
+    fmt, fs,
+    path::Path,
+    thread,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use eyre::{bail, ensure, Context, Result};
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+/// The session cookie, takes precedence over the session file.
+pub const SESSION_VAR: &str = "AOC_SESSION";
+
+const USER_AGENT: &str = concat!(
+    "github.com/TimoFreiberg/AdventOfCode aoc/",
+    env!("CARGO_PKG_VERSION")
+);
+
+/// The `session` cookie of adventofcode.com. Never printed, not even in debug output.
+pub struct Session(String);
+
+impl Session {
+    pub fn new(cookie: &str) -> Self {
+        Session(cookie.trim().to_string())
+    }
+
+    /// Reads the session from `AOC_SESSION`, falling back to `file`.
+    pub fn load(file: &Path) -> Result<Self> {
+        if let Ok(cookie) = std::env::var(SESSION_VAR) {
+            return Ok(Session::new(&cookie));
+        }
+        let cookie = fs::read_to_string(file).wrap_err_with(|| {
+            format!(
+                "no session cookie: set {SESSION_VAR} or store it in {}",
+                file.display()
+            )
+        })?;
+        Ok(Session::new(&cookie))
+    }
+}
+
+impl fmt::Debug for Session {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Session(<redacted>)")
+    }
+}
+
+pub enum Fetched {
+    Created,
+    AlreadyExists,
+    Locked,
+}
+
+/// Talks to adventofcode.com, waiting at least `interval` between two requests.
+pub struct Client {
+    agent: ureq::Agent,
+    base_url: String,
+    session: Session,
+    interval: Duration,
+    last_request: Option<Instant>,
+}
+
+impl Client {
+    pub fn new(base_url: &str, session: Session, interval: Duration) -> Self {
+        Client {
+            agent: ureq::AgentBuilder::new()
+                .user_agent(USER_AGENT)
+                .timeout(Duration::from_secs(30))
+                .build(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session,
+            interval,
+            last_request: None,
+        }
+    }
+
+    /// Stores the input of a day at `path` unless it exists already or the day is still locked.
+    pub fn fetch_missing(&mut self, year: u32, day: u32, path: &Path) -> Result<Fetched> {
+        if path.exists() {
+            return Ok(Fetched::AlreadyExists);
+        }
+        if SystemTime::now() < unlock_time(year, day)? {
+            return Ok(Fetched::Locked);
+        }
+        let input = self.fetch(year, day)?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .wrap_err_with(|| format!("failed to create {}", dir.display()))?;
+        }
+        fs::write(path, input).wrap_err_with(|| format!("failed to write {}", path.display()))?;
+        Ok(Fetched::Created)
+    }
+
+    fn fetch(&mut self, year: u32, day: u32) -> Result<String> {
+        let url = format!("{}/{year}/day/{day}/input", self.base_url);
+        let response = self.request("GET", &url).call();
+        body(&url, response)
+    }
+
+    /// The puzzle page of a day, which includes part 2 once part 1 is solved.
+    pub fn puzzle(&mut self, year: u32, day: u32) -> Result<String> {
+        let url = format!("{}/{year}/day/{day}", self.base_url);
+        let response = self.request("GET", &url).call();
+        body(&url, response)
+    }
+
+    /// The JSON of a private leaderboard. The site asks to fetch it at most every 15 minutes.
+    pub fn leaderboard(&mut self, year: u32, id: u64) -> Result<String> {
+        let url = format!(
+            "{}/{year}/leaderboard/private/view/{id}.json",
+            self.base_url
+        );
+        let response = self.request("GET", &url).call();
+        body(&url, response)
+    }
+
+    /// Posts an answer and returns the page telling whether it was right.
+    pub fn submit(&mut self, year: u32, day: u32, part: u32, answer: &str) -> Result<String> {
+        let url = format!("{}/{year}/day/{day}/answer", self.base_url);
+        let response = self
+            .request("POST", &url)
+            .send_form(&[("level", &part.to_string()), ("answer", answer)]);
+        body(&url, response)
+    }
+
+    /// Waits for the interval to pass and prepares a request carrying the session.
+    fn request(&mut self, method: &str, url: &str) -> ureq::Request {
+        if let Some(last) = self.last_request {
+            thread::sleep(self.interval.saturating_sub(last.elapsed()));
+        }
+        self.last_request = Some(Instant::now());
+        self.agent
+            .request(method, url)
+            .set("Cookie", &format!("session={}", self.session.0))
+    }
+}
+
+fn body(url: &str, response: Result<ureq::Response, ureq::Error>) -> Result<String> {
+    match response {
+        Ok(response) => Ok(response.into_string()?),
+        Err(ureq::Error::Status(400 | 401, _)) => {
+            bail!("{url} rejected the session cookie, it may have expired")
+        }
+        Err(ureq::Error::Status(code, _)) => bail!("{url} answered with status {code}"),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// The year of the first Advent of Code.
+pub const FIRST_YEAR: u32 = 2015;
+
+/// Puzzles unlock at midnight US Eastern time, which is UTC-5 in December. Fails for years
+/// without puzzles.
+pub fn unlock_time(year: u32, day: u32) -> Result<SystemTime> {
+    ensure!(
+        year >= FIRST_YEAR,
+        "{year} is before the first Advent of Code in {FIRST_YEAR}"
+    );
+    let days = days_from_civil(year.into(), 12, day.into());
+    let secs = days * 24 * 60 * 60 + 5 * 60 * 60;
+    Ok(UNIX_EPOCH + Duration::from_secs(secs.try_into()?))
+}
+
+/// Days since 1970-01-01 of a date in the proleptic Gregorian calendar.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unlock() {
+        // 2022-12-01T05:00:00Z
+        assert_eq!(
+            unlock_time(2022, 1)
+                .unwrap()
+                .duration_since(UNIX_EPOCH)
+                .unwrap(),
+            Duration::from_secs(1669870800)
+        );
+        assert_eq!(
+            unlock_time(1969, 1).unwrap_err().to_string(),
+            "1969 is before the first Advent of Code in 2015"
+        );
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        assert_eq!(days_from_civil(2000, 3, 1), 11017);
+    }
+
+    #[test]
+    fn session_is_redacted() {
+        let session = Session::new("53616c7465645f5f\n");
+        assert_eq!(session.0, "53616c7465645f5f");
+        assert!(!format!("{session:?}").contains("5361"));
+    }
+}
@@ -0,0 +1,284 @@
+//! Reads the JSON of a private leaderboard and reports solve times and local scores.
+
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    fmt::Write,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use eyre::{Context, Result};
+use serde::Deserialize;
+
+use crate::{fetch, selection::Selection};
+
+/// Stars that gave no points, as the site had problems while they were up for grabs.
+const UNSCORED: &[(u32, u32)] = &[(2018, 6), (2020, 1)];
+
+/// `/{year}/leaderboard/private/view/{id}.json`, fields the report doesn't need are left out.
+#[derive(Deserialize)]
+pub struct Leaderboard {
+    pub event: String,
+    pub members: HashMap<String, Member>,
+}
+
+#[derive(Deserialize)]
+pub struct Member {
+    pub id: u64,
+    /// Missing for anonymous members.
+    pub name: Option<String>,
+    pub stars: u32,
+    pub local_score: u32,
+    /// The stars of every day by part.
+    #[serde(default)]
+    pub completion_day_level: BTreeMap<u32, BTreeMap<u32, Star>>,
+}
+
+#[derive(Deserialize, Clone, Copy)]
+pub struct Star {
+    /// Seconds since the Unix epoch.
+    pub get_star_ts: u64,
+    /// Orders stars won in the same second.
+    #[serde(default)]
+    pub star_index: u64,
+}
+
+impl Member {
+    /// The name the site shows.
+    pub fn name(&self) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => format!("(anonymous user #{})", self.id),
+        }
+    }
+
+    fn star(&self, day: u32, part: u32) -> Option<Star> {
+        self.completion_day_level.get(&day)?.get(&part).copied()
+    }
+
+    /// Seconds from the puzzle's `unlock` until the star of a part.
+    pub fn solve_time(&self, unlock: SystemTime, day: u32, part: u32) -> Option<u64> {
+        let star = UNIX_EPOCH + Duration::from_secs(self.star(day, part)?.get_star_ts);
+        Some(star.duration_since(unlock).unwrap_or_default().as_secs())
+    }
+}
+
+impl Leaderboard {
+    pub fn parse(json: &str) -> Result<Self> {
+        serde_json::from_str(json).wrap_err("invalid leaderboard")
+    }
+
+    pub fn year(&self) -> Result<u32> {
+        self.event
+            .parse()
+            .wrap_err_with(|| format!("invalid event {:?}", self.event))
+    }
+
+    /// The members ordered by id, for results that don't depend on the JSON's order.
+    fn members(&self) -> Vec<&Member> {
+        let mut members: Vec<&Member> = self.members.values().collect();
+        members.sort_by_key(|member| member.id);
+        members
+    }
+
+    /// Local scores by member id. Of `n` members, the first to get a star gets `n` points, the
+    /// second `n - 1` and so on.
+    pub fn scores(&self) -> Result<HashMap<u64, u32>> {
+        let year = self.year()?;
+        let members = self.members();
+        let mut scores: HashMap<u64, u32> = members.iter().map(|m| (m.id, 0)).collect();
+        let days: BTreeSet<u32> = members
+            .iter()
+            .flat_map(|member| member.completion_day_level.keys().copied())
+            .collect();
+        for day in days {
+            if UNSCORED.contains(&(year, day)) {
+                continue;
+            }
+            for part in [1, 2] {
+                let mut stars: Vec<(Star, u64)> = members
+                    .iter()
+                    .filter_map(|member| Some((member.star(day, part)?, member.id)))
+                    .collect();
+                stars.sort_by_key(|(star, _)| (star.get_star_ts, star.star_index));
+                for (rank, (_, id)) in stars.into_iter().enumerate() {
+                    *scores.get_mut(&id).expect("every member has a score") +=
+                        (members.len() - rank) as u32;
+                }
+            }
+        }
+        Ok(scores)
+    }
+
+    /// A table of solve times for every selected day, followed by the standings.
+    pub fn report(&self, days: &Selection) -> Result<String> {
+        let year = self.year()?;
+        let members = self.members();
+        let mut report = String::new();
+        for day in (1..=25).filter(|day| days.contains(*day)) {
+            let mut solved: Vec<&&Member> = members
+                .iter()
+                .filter(|member| member.star(day, 1).is_some())
+                .collect();
+            if solved.is_empty() {
+                continue;
+            }
+            let unlock = fetch::unlock_time(year, day)?;
+            solved.sort_by_key(|member| {
+                let time = |part| member.solve_time(unlock, day, part).unwrap_or(u64::MAX);
+                (time(2), time(1), member.id)
+            });
+            let mut lines = vec![[
+                format!("{year} day{day}"),
+                "part 1".to_string(),
+                "part 2".to_string(),
+                "delta".to_string(),
+            ]];
+            for member in solved {
+                let part1 = member.solve_time(unlock, day, 1);
+                let part2 = member.solve_time(unlock, day, 2);
+                // Missing if the site reports part 2 before part 1.
+                let delta = part1
+                    .zip(part2)
+                    .and_then(|(part1, part2)| part2.checked_sub(part1));
+                lines.push([member.name(), time(part1), time(part2), time(delta)]);
+            }
+            writeln!(report, "{}", table(&lines))?;
+        }
+
+        let scores = self.scores()?;
+        let mut standings = members;
+        standings.sort_by_key(|member| (std::cmp::Reverse(scores[&member.id]), member.id));
+        let mut lines = vec![[
+            "rank".to_string(),
+            "member".to_string(),
+            "score".to_string(),
+            "stars".to_string(),
+        ]];
+        for (rank, member) in standings.into_iter().enumerate() {
+            let score = scores[&member.id];
+            // The site's score can only differ if the JSON is older than some stars.
+            let score = if score == member.local_score {
+                score.to_string()
+            } else {
+                format!("{score} (site: {})", member.local_score)
+            };
+            lines.push([
+                format!("{}.", rank + 1),
+                member.name(),
+                score,
+                member.stars.to_string(),
+            ]);
+        }
+        report.push_str(&table(&lines));
+        Ok(report)
+    }
+}
+
+/// `h:mm:ss`, the hours go past 24.
+fn time(seconds: Option<u64>) -> String {
+    match seconds {
+        Some(s) => format!("{}:{:02}:{:02}", s / 3600, s / 60 % 60, s % 60),
+        None => "-".to_string(),
+    }
+}
+
+fn table<const N: usize>(lines: &[[String; N]]) -> String {
+    let mut widths = [0; N];
+    for line in lines {
+        for (width, cell) in widths.iter_mut().zip(line) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+    let mut table = String::new();
+    for line in lines {
+        let cells: Vec<String> = line
+            .iter()
+            .zip(widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .collect();
+        table.push_str(cells.join("  ").trim_end());
+        table.push('\n');
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture() -> Leaderboard {
+        Leaderboard::parse(include_str!("../tests/fixtures/leaderboard.json")).unwrap()
+    }
+
+    #[test]
+    fn scores() {
+        let leaderboard = fixture();
+        let scores = leaderboard.scores().unwrap();
+        for member in leaderboard.members.values() {
+            assert_eq!(scores[&member.id], member.local_score, "{}", member.name());
+        }
+        assert_eq!(scores[&1002], 11);
+    }
+
+    #[test]
+    fn solve_times() {
+        let leaderboard = fixture();
+        let unlock = |day| fetch::unlock_time(2022, day).unwrap();
+        let alice = &leaderboard.members["1001"];
+        assert_eq!(alice.solve_time(unlock(1), 1, 1), Some(300));
+        assert_eq!(alice.solve_time(unlock(2), 2, 2), Some(1500));
+        let anonymous = &leaderboard.members["1003"];
+        assert_eq!(anonymous.name(), "(anonymous user #1003)");
+        assert_eq!(anonymous.solve_time(unlock(1), 1, 1), Some(90000));
+        assert_eq!(anonymous.solve_time(unlock(1), 1, 2), None);
+        assert_eq!(time(Some(90000)), "25:00:00");
+    }
+
+    #[test]
+    fn report() {
+        let report = fixture().report(&"all".parse().unwrap()).unwrap();
+        assert_eq!(
+            report,
+            "\
+2022 day1               part 1    part 2   delta
+Alice                   0:05:00   0:10:00  0:05:00
+Bob                     0:03:20   0:15:00  0:11:40
+(anonymous user #1003)  25:00:00  -        -
+
+2022 day2  part 1   part 2   delta
+Alice      0:16:40  0:25:00  0:08:20
+Bob        0:16:40  -        -
+
+rank  member                  score  stars
+1.    Alice                   14     4
+2.    Bob                     11     3
+3.    (anonymous user #1003)  2      1
+4.    Carol                   0      0
+"
+        );
+    }
+
+    #[test]
+    fn part2_before_part1() {
+        let mut leaderboard = fixture();
+        let alice = leaderboard.members.get_mut("1001").unwrap();
+        let day1 = alice.completion_day_level.get_mut(&1).unwrap();
+        day1.get_mut(&2).unwrap().get_star_ts = day1[&1].get_star_ts - 60;
+        let report = leaderboard.report(&"1".parse().unwrap()).unwrap();
+        assert!(
+            report.contains("Alice                   0:05:00   0:04:00  -\n"),
+            "{report}"
+        );
+    }
+
+    #[test]
+    fn unscored_days() {
+        let mut leaderboard = fixture();
+        leaderboard.event = "2020".to_string();
+        let scores = leaderboard.scores().unwrap();
+        // Only day 2 counts.
+        assert_eq!(scores[&1001], 7);
+        assert_eq!(scores[&1002], 4);
+        assert_eq!(scores[&1003], 0);
+    }
+}
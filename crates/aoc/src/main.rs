@@ -0,0 +1,747 @@
+use std::{
+    env,
+    ffi::OsStr,
+    path::{Path, PathBuf},
+    process,
+    time::{Duration, Instant, SystemTime},
+};
+
+use aoc_common::{input, shape, Answer, InputError, InputSource, Puzzle};
+use clap::{Args, Parser, Subcommand};
+use eyre::{bail, WrapErr};
+use rayon::prelude::*;
+use tracing_chrome::{ChromeLayerBuilder, FlushGuard};
+use tracing_subscriber::{filter::LevelFilter, prelude::*, EnvFilter};
+
+use answers::Answers;
+use fetch::{Client, Fetched, Session};
+use report::{Record, Report};
+use selection::Selection;
+use submit::{Attempt, History, Response, Verdict};
+use timing::{Row, Stats};
+
+mod answers;
+mod examples;
+mod fetch;
+mod leaderboard;
+mod panics;
+mod puzzles;
+mod registry;
+mod report;
+mod scaffold;
+mod selection;
+mod submit;
+mod timing;
+mod watch;
+
+/// Runs Advent of Code solutions of every year.
+#[derive(Parser)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+    /// Years to run, e.g. `2021`, `2020..=2022` or `all`
+    #[arg(short, long, default_value = "all")]
+    year: Selection,
+    /// Days to run, e.g. `15`, `1..=10`, `1,3,5` or `all`
+    #[arg(short, long, default_value = "all")]
+    day: Selection,
+    /// Parts to run, `1`, `2` or `all`
+    #[arg(short, long, default_value = "all")]
+    part: Selection,
+    /// Only list the selected days instead of running them
+    #[arg(short, long)]
+    list: bool,
+    /// Check the answers against the confirmed ones in `answers/{year}.toml`
+    #[arg(long)]
+    verify: bool,
+    /// Run the examples in `examples/{year}/day{day}` instead of the puzzle inputs
+    #[arg(long, conflicts_with_all = ["verify", "input", "input_dir"])]
+    examples: bool,
+    /// Check the inputs against the shape their day declares instead of solving them
+    #[arg(long, conflicts_with_all = ["examples", "verify"])]
+    check_input: bool,
+    /// Print how long parsing and each part took
+    #[arg(short, long)]
+    time: bool,
+    /// Run every part N times and report the mean and minimum time
+    #[arg(long, value_name = "N")]
+    bench: Option<u32>,
+    /// Flag days whose parts take longer than this many milliseconds in total
+    #[arg(long, value_name = "MS")]
+    budget: Option<u64>,
+    /// Solve the selected days concurrently and finish with a summary. Results are printed in
+    /// order once every day is done
+    #[arg(long)]
+    parallel: bool,
+    /// Print one JSON object per part instead of text
+    #[arg(long)]
+    json: bool,
+    /// Write the spans and debug events of the run to FILE as a Chrome trace, which
+    /// `chrome://tracing` or Perfetto can show
+    #[arg(long, value_name = "FILE")]
+    trace: Option<PathBuf>,
+    /// Directory holding `{year}/{day}` input files, overrides `AOC_INPUT_DIR`
+    #[arg(long, value_name = "DIR", conflicts_with = "input")]
+    input_dir: Option<PathBuf>,
+    /// Input file for a single selected day, `-` reads stdin
+    #[arg(short, long, value_name = "FILE")]
+    input: Option<PathBuf>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Download missing inputs into the input directory
+    Fetch(FetchArgs),
+    /// Create a solution skeleton for a day and register it, creating the year's crate if
+    /// needed
+    NewDay {
+        #[arg(short, long)]
+        year: u32,
+        #[arg(short, long)]
+        day: u32,
+    },
+    /// Solve a day and run its examples again whenever its solution, input or examples change
+    Watch(WatchArgs),
+    /// Download puzzle descriptions into the puzzle cache, extracting their text and examples
+    Describe(DescribeArgs),
+    /// Print a cached puzzle description
+    Show {
+        #[arg(short, long)]
+        year: u32,
+        #[arg(short, long)]
+        day: u32,
+        /// Only print this part
+        #[arg(short, long, value_parser = clap::value_parser!(u32).range(1..=2))]
+        part: Option<u32>,
+        /// Directory holding the cached descriptions, `puzzles/` by default
+        #[arg(long, value_name = "DIR")]
+        cache_dir: Option<PathBuf>,
+    },
+    /// Report solve times and local scores of a private leaderboard
+    Leaderboard(LeaderboardArgs),
+    /// Solve a part and submit the answer, unless it's known to be wrong. Correct answers are
+    /// added to `answers/{year}.toml`
+    Submit(SubmitArgs),
+    /// Check exported password entries against a 2020 day2 policy and list the ones that fail
+    AuditPasswords(AuditArgs),
+}
+
+#[derive(Args)]
+struct FetchArgs {
+    #[arg(short, long)]
+    year: u32,
+    /// Days to download, e.g. `15`, `1..=10` or `all`
+    #[arg(short, long, default_value = "all")]
+    day: Selection,
+    /// Directory to store `{year}/{day}` input files in, overrides `AOC_INPUT_DIR`
+    #[arg(long, value_name = "DIR")]
+    input_dir: Option<PathBuf>,
+    /// File holding the session cookie, unless `AOC_SESSION` is set
+    #[arg(long, value_name = "FILE", default_value = "session.cookie")]
+    session_file: PathBuf,
+    #[arg(long, value_name = "URL", default_value = fetch::DEFAULT_BASE_URL)]
+    base_url: String,
+    /// Minimum time between two requests in milliseconds
+    #[arg(long, value_name = "MS", default_value_t = 1000)]
+    interval: u64,
+}
+
+#[derive(Args)]
+struct DescribeArgs {
+    #[arg(short, long)]
+    year: u32,
+    /// Days to download, e.g. `15`, `1..=10` or `all`
+    #[arg(short, long, default_value = "all")]
+    day: Selection,
+    /// Directory holding the cached descriptions, `puzzles/` by default
+    #[arg(long, value_name = "DIR")]
+    cache_dir: Option<PathBuf>,
+    /// File holding the session cookie, unless `AOC_SESSION` is set
+    #[arg(long, value_name = "FILE", default_value = "session.cookie")]
+    session_file: PathBuf,
+    #[arg(long, value_name = "URL", default_value = fetch::DEFAULT_BASE_URL)]
+    base_url: String,
+    /// Minimum time between two requests in milliseconds
+    #[arg(long, value_name = "MS", default_value_t = 1000)]
+    interval: u64,
+}
+
+#[derive(Args)]
+struct WatchArgs {
+    #[arg(short, long)]
+    year: u32,
+    #[arg(short, long)]
+    day: u32,
+    /// Directory holding `{year}/{day}` input files, overrides `AOC_INPUT_DIR`
+    #[arg(long, value_name = "DIR")]
+    input_dir: Option<PathBuf>,
+}
+
+#[derive(Args)]
+#[command(group = clap::ArgGroup::new("source").required(true).args(["file", "id"]))]
+struct LeaderboardArgs {
+    /// Read the leaderboard JSON from FILE instead of downloading it
+    #[arg(long, value_name = "FILE")]
+    file: Option<PathBuf>,
+    /// Download the leaderboard with this id, the one in its URL
+    #[arg(long, requires = "year")]
+    id: Option<u64>,
+    #[arg(short, long)]
+    year: Option<u32>,
+    /// Days to report solve times of, e.g. `15`, `1..=10` or `all`
+    #[arg(short, long, default_value = "all")]
+    day: Selection,
+    /// File holding the session cookie, unless `AOC_SESSION` is set
+    #[arg(long, value_name = "FILE", default_value = "session.cookie")]
+    session_file: PathBuf,
+    #[arg(long, value_name = "URL", default_value = fetch::DEFAULT_BASE_URL)]
+    base_url: String,
+}
+
+#[derive(Args)]
+struct SubmitArgs {
+    #[arg(short, long)]
+    year: u32,
+    #[arg(short, long)]
+    day: u32,
+    #[arg(short, long, value_parser = clap::value_parser!(u32).range(1..=2))]
+    part: u32,
+    /// Submit this instead of solving the part
+    #[arg(long)]
+    answer: Option<String>,
+    /// Directory holding `{year}/{day}` input files, overrides `AOC_INPUT_DIR`
+    #[arg(long, value_name = "DIR")]
+    input_dir: Option<PathBuf>,
+    /// Directory with the confirmed answers, `answers/` by default
+    #[arg(long, value_name = "DIR")]
+    answers_dir: Option<PathBuf>,
+    /// File recording every submitted answer, `submissions.jsonl` by default
+    #[arg(long, value_name = "FILE")]
+    history: Option<PathBuf>,
+    /// File holding the session cookie, unless `AOC_SESSION` is set
+    #[arg(long, value_name = "FILE", default_value = "session.cookie")]
+    session_file: PathBuf,
+    #[arg(long, value_name = "URL", default_value = fetch::DEFAULT_BASE_URL)]
+    base_url: String,
+}
+
+#[derive(Args)]
+struct AuditArgs {
+    /// Policy to check, e.g. `count`, `positions` or `all(count, not(positions))`
+    #[arg(long, default_value = "count")]
+    policy: String,
+    /// File with one `{min}-{max} {letter}: {password}` entry per line, `-` reads stdin. The
+    /// 2020 day2 input by default
+    #[arg(short, long, value_name = "FILE")]
+    input: Option<PathBuf>,
+}
+
+impl Cli {
+    fn input_source(&self) -> InputSource {
+        match (&self.input, &self.input_dir) {
+            (Some(path), _) if path.as_os_str() == "-" => InputSource::Stdin,
+            (Some(path), _) => InputSource::File(path.clone()),
+            (None, Some(dir)) => InputSource::Dir(dir.clone()),
+            (None, None) => InputSource::from_env(),
+        }
+    }
+}
+
+fn main() -> eyre::Result<()> {
+    let cli = Cli::parse();
+    let _trace = init_tracing(cli.trace.as_deref());
+
+    match cli.command {
+        Some(Command::Fetch(args)) => fetch(args),
+        Some(Command::NewDay { year, day }) => {
+            for path in scaffold::new_day(workspace_root(), year, day)? {
+                println!("wrote {}", path.display());
+            }
+            Ok(())
+        }
+        Some(Command::Watch(args)) => watch(args),
+        Some(Command::Describe(args)) => describe(args),
+        Some(Command::Show {
+            year,
+            day,
+            part,
+            cache_dir,
+        }) => {
+            let dir = cache_dir.unwrap_or_else(puzzles::default_dir);
+            print!("{}", puzzles::show(&dir, year, day, part)?);
+            Ok(())
+        }
+        Some(Command::Leaderboard(args)) => leaderboard(args),
+        Some(Command::Submit(args)) => submit(args),
+        Some(Command::AuditPasswords(args)) => audit_passwords(args),
+        None => run(cli),
+    }
+}
+
+/// Logs to stdout, filtered by `RUST_LOG` and showing `info` and above by default. With a
+/// `trace` file, everything down to `debug` also goes there. The trace is complete once the
+/// returned guard is dropped.
+fn init_tracing(trace: Option<&Path>) -> Option<FlushGuard> {
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
+    let log = tracing_subscriber::fmt::layer().with_filter(filter);
+    let (chrome, guard) = match trace {
+        Some(path) => {
+            let (chrome, guard) = ChromeLayerBuilder::new()
+                .file(path)
+                .include_args(true)
+                .build();
+            (Some(chrome.with_filter(LevelFilter::DEBUG)), Some(guard))
+        }
+        None => (None, None),
+    };
+    tracing_subscriber::registry().with(log).with(chrome).init();
+    guard
+}
+
+fn workspace_root() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .ancestors()
+        .nth(2)
+        .expect("crate is inside the workspace")
+}
+
+fn run(cli: Cli) -> eyre::Result<()> {
+    let selected: Vec<&Puzzle> = registry::puzzles()
+        .filter(|p| cli.year.contains(p.year) && cli.day.contains(p.day))
+        .collect();
+    if selected.is_empty() {
+        bail!("No solved day matches the selection");
+    }
+
+    if cli.list {
+        for puzzle in selected {
+            println!("{} day{}", puzzle.year, puzzle.day);
+        }
+        return Ok(());
+    }
+    if cli.input.is_some() && selected.len() > 1 {
+        bail!(
+            "--input needs a selection of a single day, but {} match",
+            selected.len()
+        );
+    }
+
+    if cli.examples {
+        return run_examples(&cli, &selected);
+    }
+    if cli.check_input {
+        return check_inputs(&cli, &selected);
+    }
+
+    let source = cli.input_source();
+    let mut answers = cli.verify.then(|| Answers::new(answers::default_dir()));
+    let mut report = Report::new(cli.json);
+    let timed = cli.time || cli.bench.is_some() || cli.budget.is_some();
+    let runs = cli.bench.unwrap_or(1);
+    let mut rows = Vec::new();
+    // Time spent in every part, including failed ones.
+    let mut busy = Duration::ZERO;
+    let parts: Vec<u32> = [1, 2]
+        .into_iter()
+        .filter(|part| cli.part.contains(*part))
+        .collect();
+
+    let solve = |puzzle: &&Puzzle| solve_day(puzzle, &source, &parts, runs, timed);
+    let mut finish = |puzzle: &Puzzle, solved: Result<SolvedDay, InputError>| {
+        let solved = match solved {
+            Ok(solved) => solved,
+            Err(e) => {
+                for &part in &parts {
+                    report.record(&Record::failed(puzzle, part, e.to_string()));
+                }
+                return Ok(());
+            }
+        };
+        if let Some(e) = solved.parse_error {
+            eprintln!("{} day{} failed to parse: {:?}", puzzle.year, puzzle.day, e);
+        }
+        let mut row = solved.row;
+        for (part, result) in solved.parts {
+            let record = match result {
+                Ok((answer, stats)) => {
+                    busy += stats.mean;
+                    row.parts[part as usize - 1] = Some(stats);
+                    let expected = match &mut answers {
+                        Some(answers) => Some(answers.get(puzzle.year, puzzle.day, part)?),
+                        None => None,
+                    };
+                    Record::solved(puzzle, part, answer.to_string(), expected).with_stats(stats)
+                }
+                Err(e) => Record::failed(puzzle, part, format!("{e:?}")),
+            };
+            report.record(&record);
+        }
+        rows.push(row);
+        eyre::Ok(())
+    };
+
+    panics::install_hook();
+    let started = Instant::now();
+    if cli.parallel {
+        let solved: Vec<_> = selected.par_iter().map(solve).collect();
+        for (puzzle, solved) in selected.iter().zip(solved) {
+            finish(puzzle, solved)?;
+        }
+    } else {
+        for puzzle in &selected {
+            finish(puzzle, solve(puzzle))?;
+        }
+    }
+    let elapsed = started.elapsed();
+
+    if timed && !cli.json {
+        println!();
+        timing::print_table(&rows, cli.budget.map(Duration::from_millis));
+    }
+    if cli.verify && !cli.json {
+        println!(
+            "{} passed, {} failed, {} missing",
+            report.passed, report.failed, report.missing
+        );
+    }
+    if cli.parallel && !cli.json {
+        report.print_summary(selected.len(), elapsed, busy);
+    }
+    let failures = report.failed + report.errors;
+    if failures > 0 {
+        bail!("{failures} part(s) failed");
+    }
+    Ok(())
+}
+
+/// What solving a day produced, before the answers are checked and reported.
+struct SolvedDay {
+    /// Without part timings, those are only filled in for parts that succeed.
+    row: Row,
+    parse_error: Option<eyre::Report>,
+    /// Failed parts have no timings, as they didn't run as often as asked.
+    parts: Vec<(u32, eyre::Result<(Answer, Stats)>)>,
+}
+
+/// Reads the input of a day and solves `parts` of it. Panics are turned into errors of the
+/// part or parsing step they happened in.
+fn solve_day(
+    puzzle: &Puzzle,
+    source: &InputSource,
+    parts: &[u32],
+    runs: u32,
+    timed: bool,
+) -> Result<SolvedDay, InputError> {
+    let input = registry::input(puzzle, source)?;
+    let mut row = Row::new(puzzle);
+    let mut parse_error = None;
+    if timed {
+        match timing::measure(runs, || panics::catch(|| puzzle.parse(&input).transpose())) {
+            Ok((Some(()), stats)) => row.parse = Some(stats),
+            Ok((None, _)) => {}
+            Err(e) => parse_error = Some(e),
+        }
+    }
+    let parts = parts
+        .iter()
+        .map(|&part| {
+            let result = timing::measure(runs, || panics::catch(|| puzzle.part(part, &input)));
+            (part, result)
+        })
+        .collect();
+    Ok(SolvedDay {
+        row,
+        parse_error,
+        parts,
+    })
+}
+
+/// Runs the selected parts on every example with an answer for them.
+fn run_examples(cli: &Cli, selected: &[&Puzzle]) -> eyre::Result<()> {
+    let dir = examples::default_dir();
+    let mut report = Report::new(cli.json);
+    panics::install_hook();
+    for puzzle in selected {
+        for example in examples::load(&dir, puzzle.year, puzzle.day)? {
+            for part in [1, 2].into_iter().filter(|part| cli.part.contains(*part)) {
+                let Some(expected) = &example.expected[part as usize - 1] else {
+                    continue;
+                };
+                let result =
+                    timing::measure(1, || panics::catch(|| puzzle.part(part, &example.input)));
+                let record = match result {
+                    Ok((answer, stats)) => Record::solved(
+                        puzzle,
+                        part,
+                        answer.to_string(),
+                        Some(Some(expected.clone())),
+                    )
+                    .with_stats(stats),
+                    Err(e) => Record::failed(puzzle, part, format!("{e:?}")),
+                };
+                report.record(&record.with_example(&example.name));
+            }
+        }
+    }
+    if !cli.json {
+        println!("{} passed, {} failed", report.passed, report.failed);
+    }
+    let failures = report.failed + report.errors;
+    if failures > 0 {
+        bail!("{failures} example(s) failed");
+    }
+    Ok(())
+}
+
+/// Reports where inputs differ from what their day expects, at most this many places per day.
+const MISMATCHES_SHOWN: usize = 10;
+
+fn check_inputs(cli: &Cli, selected: &[&Puzzle]) -> eyre::Result<()> {
+    let source = cli.input_source();
+    let (mut ok, mut mismatched, mut missing) = (0, 0, 0);
+    for puzzle in selected {
+        let label = format!("{} day{}", puzzle.year, puzzle.day);
+        let input = match source.read(puzzle.year, puzzle.day) {
+            Ok(input) => input,
+            Err(InputError::Missing { .. })
+                if matches!(source, InputSource::Dir(_))
+                    && puzzle.solution.embedded_input().is_some() =>
+            {
+                println!("{label}: uses its embedded input");
+                continue;
+            }
+            Err(e) => {
+                missing += 1;
+                println!("{label}: {e}");
+                continue;
+            }
+        };
+        let shape = puzzle.solution.input_shape();
+        let errors = shape::check(puzzle.day, &input, shape.as_ref());
+        if errors.is_empty() {
+            ok += 1;
+            let undeclared = if shape.is_none() {
+                ", no shape declared"
+            } else {
+                ""
+            };
+            println!("{label}: ok{undeclared}");
+            continue;
+        }
+        mismatched += 1;
+        for e in errors.iter().take(MISMATCHES_SHOWN) {
+            eprintln!("{} {e}", puzzle.year);
+        }
+        if errors.len() > MISMATCHES_SHOWN {
+            eprintln!("{label}: {} more", errors.len() - MISMATCHES_SHOWN);
+        }
+    }
+    println!("{ok} ok, {mismatched} mismatched, {missing} missing");
+    if mismatched > 0 {
+        bail!("{mismatched} input(s) don't look like their day expects");
+    }
+    Ok(())
+}
+
+/// Rebuilds and runs the day through cargo every time, so changes to the solution are picked up.
+fn watch(args: WatchArgs) -> eyre::Result<()> {
+    // Cargo runs the day from the workspace root.
+    let input_dir = std::path::absolute(args.input_dir.unwrap_or_else(input::default_dir))?;
+    let watched = watch::Watched::new(
+        workspace_root(),
+        &input_dir,
+        &examples::default_dir(),
+        args.year,
+        args.day,
+    );
+    let cargo = env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+    let (year, day) = (args.year.to_string(), args.day.to_string());
+    let aoc = |extra: &[&OsStr]| {
+        let status = process::Command::new(&cargo)
+            .current_dir(workspace_root())
+            .args(["run", "--release", "--quiet", "--package", "aoc", "--"])
+            .args(["--year", &year, "--day", &day])
+            .args(extra)
+            .status();
+        if let Err(e) = status {
+            eprintln!("failed to run cargo: {e}");
+        }
+    };
+
+    watch::watch(&watched, || {
+        println!("\n--- {} day{} ---", args.year, args.day);
+        aoc(&[
+            "--time".as_ref(),
+            "--input-dir".as_ref(),
+            input_dir.as_os_str(),
+        ]);
+        aoc(&["--examples".as_ref()]);
+    })
+}
+
+fn fetch(args: FetchArgs) -> eyre::Result<()> {
+    let dir = args.input_dir.unwrap_or_else(input::default_dir);
+    let session = Session::load(&args.session_file)?;
+    let mut client = Client::new(
+        &args.base_url,
+        session,
+        Duration::from_millis(args.interval),
+    );
+
+    let mut failures = 0;
+    for day in (1..=25).filter(|day| args.day.contains(*day)) {
+        let path = input::input_path(&dir, args.year, day);
+        match client.fetch_missing(args.year, day, &path) {
+            Ok(Fetched::Created) => println!("{} created", path.display()),
+            Ok(Fetched::AlreadyExists) => println!("{} already exists", path.display()),
+            Ok(Fetched::Locked) => {
+                println!("{} day{} is not unlocked yet", args.year, day);
+                break;
+            }
+            Err(e) => {
+                failures += 1;
+                eprintln!("{} day{} failed: {:?}", args.year, day, e);
+            }
+        }
+    }
+    if failures > 0 {
+        bail!("{failures} download(s) failed");
+    }
+    Ok(())
+}
+
+/// Downloads the descriptions of days that aren't cached with both parts yet.
+fn describe(args: DescribeArgs) -> eyre::Result<()> {
+    let dir = args.cache_dir.unwrap_or_else(puzzles::default_dir);
+    let session = Session::load(&args.session_file)?;
+    let mut client = Client::new(
+        &args.base_url,
+        session,
+        Duration::from_millis(args.interval),
+    );
+
+    let mut failures = 0;
+    for day in (1..=25).filter(|day| args.day.contains(*day)) {
+        let label = format!("{} day{}", args.year, day);
+        if SystemTime::now() < fetch::unlock_time(args.year, day)? {
+            println!("{label} is not unlocked yet");
+            break;
+        }
+        let described = puzzles::cached_parts(&dir, args.year, day).and_then(|cached| {
+            if cached == Some(2) {
+                return Ok(None);
+            }
+            let page = client.puzzle(args.year, day)?;
+            puzzles::store(&dir, args.year, day, &page).map(Some)
+        });
+        match described {
+            Ok(Some(description)) => println!(
+                "{label}: {} part(s) and {} example(s) in {}",
+                description.parts,
+                description.examples.len(),
+                puzzles::day_dir(&dir, args.year, day).display()
+            ),
+            Ok(None) => println!("{label} is cached with both parts"),
+            Err(e) => {
+                failures += 1;
+                eprintln!("{label} failed: {e:?}");
+            }
+        }
+    }
+    if failures > 0 {
+        bail!("{failures} download(s) failed");
+    }
+    Ok(())
+}
+
+fn leaderboard(args: LeaderboardArgs) -> eyre::Result<()> {
+    let json = match (&args.file, args.id, args.year) {
+        (Some(file), _, _) => std::fs::read_to_string(file)
+            .wrap_err_with(|| format!("failed to read {}", file.display()))?,
+        (None, Some(id), Some(year)) => {
+            let session = Session::load(&args.session_file)?;
+            Client::new(&args.base_url, session, Duration::ZERO).leaderboard(year, id)?
+        }
+        _ => unreachable!("clap requires a file or an id and year"),
+    };
+    let leaderboard = leaderboard::Leaderboard::parse(&json)?;
+    print!("{}", leaderboard.report(&args.day)?);
+    Ok(())
+}
+
+fn audit_passwords(args: AuditArgs) -> eyre::Result<()> {
+    let policy = aoc2020::day2::Registry::default()
+        .parse(&args.policy)
+        .map_err(|e| eyre::eyre!("invalid policy: {e}"))?;
+    let source = match args.input {
+        Some(path) if path.as_os_str() == "-" => InputSource::Stdin,
+        Some(path) => InputSource::File(path),
+        None => InputSource::from_env(),
+    };
+    let audit = aoc2020::day2::audit(&source.read(2020, 2)?, &*policy);
+    println!("{audit}");
+    if !audit.failures.is_empty() {
+        bail!("{} entries fail {}", audit.failures.len(), audit.policy);
+    }
+    Ok(())
+}
+
+fn submit(args: SubmitArgs) -> eyre::Result<()> {
+    let label = format!("{} day{}.{}", args.year, args.day, args.part);
+    let answer = match args.answer {
+        Some(answer) => answer,
+        None => {
+            let Some(puzzle) =
+                registry::puzzles().find(|p| p.year == args.year && p.day == args.day)
+            else {
+                bail!("{} day{} is not solved", args.year, args.day);
+            };
+            let dir = args.input_dir.unwrap_or_else(input::default_dir);
+            let input = registry::input(puzzle, &InputSource::Dir(dir))?;
+            puzzle.part(args.part, &input)?.to_string()
+        }
+    };
+    if answer.trim().contains('\n') {
+        bail!("{label}: multi-line answers have to be read and passed with --answer:\n{answer}");
+    }
+    let answer = answer.trim();
+
+    let mut answers = Answers::new(args.answers_dir.unwrap_or_else(answers::default_dir));
+    match answers.get(args.year, args.day, args.part)? {
+        Some(expected) if answers::matches(&expected, answer) => {
+            println!("{label}: {answer} is the confirmed answer already");
+            return Ok(());
+        }
+        Some(expected) => bail!("{label}: {answer} differs from the confirmed answer {expected}"),
+        None => {}
+    }
+    let mut history = History::load(&args.history.unwrap_or_else(submit::default_history))?;
+    if let Err(e) = history.check(args.year, args.day, args.part, answer, submit::now()) {
+        bail!("{label}: not submitting {answer}, {e}");
+    }
+
+    let session = Session::load(&args.session_file)?;
+    let mut client = Client::new(&args.base_url, session, Duration::ZERO);
+    let page = client.submit(args.year, args.day, args.part, answer)?;
+    let response = Response::parse(&page).wrap_err_with(|| format!("{label}: {answer}"))?;
+    history.record(Attempt::new(
+        args.year, args.day, args.part, answer, &response,
+    ))?;
+
+    let wait = match response.wait {
+        Some(wait) => format!(", wait {}s before the next answer", wait.as_secs()),
+        None => String::new(),
+    };
+    match response.verdict {
+        Verdict::Correct => {
+            answers.record(args.year, args.day, args.part, answer)?;
+            println!("{label}: {answer} {}{wait}", response.verdict);
+            Ok(())
+        }
+        Verdict::NotChecked => {
+            println!("{label}: {answer} {}{wait}", response.verdict);
+            Ok(())
+        }
+        verdict => bail!("{label}: {answer} {verdict}{wait}"),
+    }
+}
@@ -0,0 +1,59 @@
+//! Turns panicking solutions into errors, so one broken day doesn't take the others down.
+
+use std::{
+    cell::{Cell, RefCell},
+    panic::{self, AssertUnwindSafe},
+};
+
+use eyre::{eyre, Result};
+
+thread_local! {
+    static CATCHING: Cell<bool> = const { Cell::new(false) };
+    static MESSAGE: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Keeps the messages of panics inside [`catch`] for its error instead of printing them. Other
+/// panics are printed as usual.
+pub fn install_hook() {
+    let default = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        if CATCHING.get() {
+            MESSAGE.set(Some(info.to_string()));
+        } else {
+            default(info);
+        }
+    }));
+}
+
+/// Runs `f`, turning a panic into an error with the panic's message and location.
+pub fn catch<T>(f: impl FnOnce() -> Result<T>) -> Result<T> {
+    let catching = CATCHING.replace(true);
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    CATCHING.set(catching);
+    result.unwrap_or_else(|payload| {
+        let message = MESSAGE.take().unwrap_or_else(|| {
+            payload
+                .downcast_ref::<&str>()
+                .map(|s| s.to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "panicked".to_string())
+        });
+        Err(eyre!("{message}"))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn catches() {
+        install_hook();
+        assert_eq!(catch(|| Ok(1)).unwrap(), 1);
+        let error = catch::<()>(|| panic!("No valid program")).unwrap_err();
+        assert!(error.to_string().contains("No valid program"), "{error}");
+        assert!(error.to_string().contains("panics.rs"), "{error}");
+        let error = catch::<()>(|| eyre::bail!("no input")).unwrap_err();
+        assert_eq!(error.to_string(), "no input");
+    }
+}
@@ -0,0 +1,229 @@
+//! A local copy of the puzzle descriptions, readable offline. Each day's page is kept in
+//! `{dir}/{year}/day{day}/` as `page.html`, together with the text as `puzzle.md` and every
+//! `<pre><code>` block as `example{n}.txt`.
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use eyre::{Context, Result};
+
+/// `puzzles/` in the workspace root.
+pub fn default_dir() -> PathBuf {
+    crate::workspace_root().join("puzzles")
+}
+
+pub fn day_dir(dir: &Path, year: u32, day: u32) -> PathBuf {
+    dir.join(year.to_string()).join(format!("day{day}"))
+}
+
+/// The parts of a description, the second one only shows up once the first is solved.
+#[derive(PartialEq, Eq, Debug)]
+pub struct Description {
+    pub markdown: String,
+    pub examples: Vec<String>,
+    pub parts: usize,
+}
+
+/// Converts the `<article>`s of a puzzle page to markdown.
+pub fn extract(page: &str) -> Description {
+    let mut markdown = Markdown::default();
+    let mut parts = 0;
+    let mut rest = page;
+    while let Some((_, article)) = rest.split_once("<article") {
+        let Some((_, article)) = article.split_once('>') else {
+            break;
+        };
+        let (article, after) = article.split_once("</article>").unwrap_or((article, ""));
+        markdown.convert(article);
+        parts += 1;
+        rest = after;
+    }
+    Description {
+        markdown: markdown.text.trim_end().to_string() + "\n",
+        examples: markdown.examples,
+        parts,
+    }
+}
+
+/// Just enough of HTML to convert the few tags puzzle descriptions use.
+#[derive(Default)]
+struct Markdown {
+    text: String,
+    examples: Vec<String>,
+    /// The `<pre>` block being read.
+    example: Option<String>,
+    in_code: bool,
+    link: Option<String>,
+}
+
+impl Markdown {
+    fn convert(&mut self, mut html: &str) {
+        while !html.is_empty() {
+            if let Some(tag) = html.strip_prefix('<') {
+                let (tag, after) = tag.split_once('>').unwrap_or((tag, ""));
+                self.tag(tag);
+                html = after;
+            } else {
+                let end = html.find('<').unwrap_or(html.len());
+                self.text(&html[..end]);
+                html = &html[end..];
+            }
+        }
+    }
+
+    fn tag(&mut self, tag: &str) {
+        let (closing, tag) = match tag.strip_prefix('/') {
+            Some(tag) => (true, tag),
+            None => (false, tag),
+        };
+        let (name, attributes) = tag.split_once(' ').unwrap_or((tag, ""));
+        match (name, closing) {
+            ("h2", false) => self.text.push_str("## "),
+            ("h2" | "p", true) => self.text.push_str("\n\n"),
+            ("pre", false) => {
+                self.text.push_str("```\n");
+                self.example = Some(String::new());
+            }
+            ("pre", true) => {
+                let example = self.example.take().unwrap_or_default();
+                if !example.ends_with('\n') {
+                    self.text.push('\n');
+                }
+                self.text.push_str("```\n\n");
+                self.examples.push(example);
+            }
+            ("code", _) if self.example.is_none() => {
+                self.in_code = !closing;
+                self.text.push('`');
+            }
+            ("em", _) if self.example.is_none() && !self.in_code => self.text.push('*'),
+            ("li", false) => self.text.push_str("- "),
+            ("li", true) => self.text.push('\n'),
+            ("ul", true) => self.text.push('\n'),
+            ("a", false) => {
+                self.link = attribute(attributes, "href").map(str::to_string);
+                self.text.push('[');
+            }
+            ("a", true) => match self.link.take() {
+                Some(href) => self.text.push_str(&format!("]({href})")),
+                None => self.text.push(']'),
+            },
+            _ => {}
+        }
+    }
+
+    fn text(&mut self, html: &str) {
+        let text = decode(html);
+        match &mut self.example {
+            Some(example) => {
+                example.push_str(&text);
+                self.text.push_str(&text);
+            }
+            // Line breaks between block tags.
+            None if text.trim().is_empty() && text.contains('\n') => {}
+            None => self.text.push_str(&text),
+        }
+    }
+}
+
+fn attribute<'a>(attributes: &'a str, name: &str) -> Option<&'a str> {
+    let (_, value) = attributes.split_once(&format!("{name}=\""))?;
+    Some(value.split_once('"')?.0)
+}
+
+fn decode(html: &str) -> String {
+    html.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+/// How many parts of a day are cached, `None` if it wasn't fetched yet.
+pub fn cached_parts(dir: &Path, year: u32, day: u32) -> Result<Option<usize>> {
+    let path = day_dir(dir, year, day).join("page.html");
+    match fs::read_to_string(&path) {
+        Ok(page) => Ok(Some(extract(&page).parts)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e).wrap_err_with(|| format!("failed to read {}", path.display())),
+    }
+}
+
+/// Replaces the cached page of a day and everything extracted from it.
+pub fn store(dir: &Path, year: u32, day: u32, page: &str) -> Result<Description> {
+    let day_dir = day_dir(dir, year, day);
+    let write = |name: &str, contents: &str| {
+        let path = day_dir.join(name);
+        fs::write(&path, contents).wrap_err_with(|| format!("failed to write {}", path.display()))
+    };
+    match fs::remove_dir_all(&day_dir) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => {
+            return Err(e).wrap_err_with(|| format!("failed to remove {}", day_dir.display()))
+        }
+        _ => {}
+    }
+    fs::create_dir_all(&day_dir)
+        .wrap_err_with(|| format!("failed to create {}", day_dir.display()))?;
+
+    let description = extract(page);
+    write("page.html", page)?;
+    write("puzzle.md", &description.markdown)?;
+    for (i, example) in description.examples.iter().enumerate() {
+        write(&format!("example{}.txt", i + 1), example)?;
+    }
+    Ok(description)
+}
+
+/// The cached markdown of a day, only the given part's if there is one.
+pub fn show(dir: &Path, year: u32, day: u32, part: Option<u32>) -> Result<String> {
+    let path = day_dir(dir, year, day).join("puzzle.md");
+    let markdown = fs::read_to_string(&path).wrap_err_with(|| {
+        format!(
+            "failed to read {}, `aoc describe --year {year} --day {day}` downloads it",
+            path.display()
+        )
+    })?;
+    let (part1, part2) = match markdown.split_once("## --- Part Two ---") {
+        Some((part1, part2)) => (part1, Some(format!("## --- Part Two ---{part2}"))),
+        None => (markdown.as_str(), None),
+    };
+    Ok(match part {
+        Some(1) => part1.trim_end().to_string() + "\n",
+        Some(_) => part2.ok_or_else(|| {
+            eyre::eyre!("part 2 of {year} day{day} isn't cached, it shows up once part 1 is solved")
+        })?,
+        None => markdown,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn markdown() {
+        let page = r#"<main>
+<article class="day-desc"><h2>--- Day 1: Counting ---</h2><p>Count the <em>numbers</em> of the <a href="/2015/about">list</a>:</p>
+<pre><code>1 &lt; 2
+<em>3</em>
+</code></pre>
+<ul>
+<li>The sum is <code><em>6</em></code>.</li>
+<li>A &amp; B.</li>
+</ul>
+</article>
+<p>Your puzzle answer was <code>123</code>.</p>
+</main>"#;
+        let description = extract(page);
+        assert_eq!(
+            description.markdown,
+            "## --- Day 1: Counting ---\n\nCount the *numbers* of the [list](/2015/about):\n\n\
+            ```\n1 < 2\n3\n```\n\n- The sum is `6`.\n- A & B.\n"
+        );
+        assert_eq!(description.examples, ["1 < 2\n3\n"]);
+        assert_eq!(description.parts, 1);
+    }
+}
@@ -0,0 +1,28 @@
+use aoc_common::{InputError, InputSource, Puzzle};
+
+/// The registries of every year crate, in order.
+const YEARS: &[&[Puzzle]] = &[
+    aoc2020::PUZZLES,
+    aoc2021::PUZZLES,
+    aoc22::PUZZLES,
+    aoc2023::PUZZLES,
+    aoc2024::PUZZLES,
+];
+
+pub fn puzzles() -> impl Iterator<Item = &'static Puzzle> {
+    YEARS.iter().flat_map(|year| year.iter())
+}
+
+/// Reads the input of a day from `source`. Falls back to the one compiled into the solution if
+/// an input directory has no file for the day, but never for an explicit `--input` file.
+pub fn input(puzzle: &Puzzle, source: &InputSource) -> Result<String, InputError> {
+    match (source.read(puzzle.year, puzzle.day), source) {
+        (Err(InputError::Missing { path }), InputSource::Dir(_)) => {
+            match puzzle.solution.embedded_input() {
+                Some(input) => Ok(input.to_string()),
+                None => Err(InputError::Missing { path }),
+            }
+        }
+        (result, _) => result,
+    }
+}
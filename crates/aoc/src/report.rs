@@ -0,0 +1,284 @@
+use std::time::Duration;
+
+use aoc_common::Puzzle;
+use serde::Serialize;
+
+use crate::{answers, timing::Stats};
+
+#[derive(Serialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum Status {
+    /// Solved, without checking the answer.
+    Ok,
+    Pass,
+    Fail,
+    /// Solved, but there is no confirmed answer to check against.
+    Missing,
+    Error,
+}
+
+/// The outcome of one part. Answers are always strings, numbers can be larger than JSON
+/// consumers handle exactly.
+#[derive(Serialize)]
+pub struct Record {
+    pub year: u32,
+    pub day: u32,
+    pub part: u32,
+    /// The name of the example the part ran on, if it didn't run on the puzzle input.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub example: Option<String>,
+    pub status: Status,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub answer: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expected: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Mean over all runs.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration_ms: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_duration_ms: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub runs: Option<u32>,
+}
+
+impl Record {
+    pub fn solved(
+        puzzle: &Puzzle,
+        part: u32,
+        answer: String,
+        expected: Option<Option<String>>,
+    ) -> Self {
+        let status = match &expected {
+            None => Status::Ok,
+            Some(Some(expected)) if answers::matches(expected, &answer) => Status::Pass,
+            Some(Some(_)) => Status::Fail,
+            Some(None) => Status::Missing,
+        };
+        Record {
+            answer: Some(answer),
+            expected: expected.flatten(),
+            ..Record::new(puzzle, part, status)
+        }
+    }
+
+    pub fn failed(puzzle: &Puzzle, part: u32, error: String) -> Self {
+        Record {
+            error: Some(error),
+            ..Record::new(puzzle, part, Status::Error)
+        }
+    }
+
+    fn new(puzzle: &Puzzle, part: u32, status: Status) -> Self {
+        Record {
+            year: puzzle.year,
+            day: puzzle.day,
+            part,
+            example: None,
+            status,
+            answer: None,
+            expected: None,
+            error: None,
+            duration_ms: None,
+            min_duration_ms: None,
+            runs: None,
+        }
+    }
+
+    pub fn with_example(self, name: &str) -> Self {
+        Record {
+            example: Some(name.to_string()),
+            ..self
+        }
+    }
+
+    pub fn with_stats(self, stats: Stats) -> Self {
+        let multiple = stats.runs > 1;
+        Record {
+            duration_ms: Some(stats.mean.as_secs_f64() * 1000.0),
+            min_duration_ms: multiple.then_some(stats.min.as_secs_f64() * 1000.0),
+            runs: multiple.then_some(stats.runs),
+            ..self
+        }
+    }
+}
+
+/// Prints records as they come in, either for humans or as one JSON object per line.
+pub struct Report {
+    json: bool,
+    /// Solved parts that weren't checked.
+    pub ok: usize,
+    pub passed: usize,
+    pub failed: usize,
+    pub missing: usize,
+    pub errors: usize,
+    /// Labels of the parts that failed or errored, in the order they were recorded.
+    pub failures: Vec<String>,
+}
+
+impl Report {
+    pub fn new(json: bool) -> Self {
+        Report {
+            json,
+            ok: 0,
+            passed: 0,
+            failed: 0,
+            missing: 0,
+            errors: 0,
+            failures: Vec::new(),
+        }
+    }
+
+    pub fn record(&mut self, record: &Record) {
+        let mut label = format!("{} day{}.{}", record.year, record.day, record.part);
+        if let Some(example) = &record.example {
+            label = format!("{label} {example}");
+        }
+        match record.status {
+            Status::Ok => self.ok += 1,
+            Status::Pass => self.passed += 1,
+            Status::Fail => self.failed += 1,
+            Status::Missing => self.missing += 1,
+            Status::Error => self.errors += 1,
+        }
+        if matches!(record.status, Status::Fail | Status::Error) {
+            self.failures.push(label.clone());
+        }
+        if self.json {
+            println!(
+                "{}",
+                serde_json::to_string(record).expect("records serialize")
+            );
+            return;
+        }
+
+        let answer = record.answer.as_deref().unwrap_or_default();
+        let expected = record.expected.as_deref().unwrap_or_default();
+        match record.status {
+            Status::Ok => println!("{label}:{}", inline(answer)),
+            Status::Pass => println!("{label}: pass"),
+            Status::Fail if expected.contains('\n') || answer.contains('\n') => {
+                println!("{label}: fail\n{}", diff(expected, answer))
+            }
+            Status::Fail => println!("{label}: fail, expected {expected} but got {answer}"),
+            Status::Missing => println!("{label}: missing, got{}", inline(answer)),
+            Status::Error => eprintln!(
+                "{label} failed: {}",
+                record.error.as_deref().unwrap_or_default()
+            ),
+        }
+    }
+
+    /// How many parts of how many days succeeded, which failed, and how long it took. `busy` is
+    /// the time spent solving, which is more than `elapsed` if days ran concurrently.
+    pub fn print_summary(&self, days: usize, elapsed: Duration, busy: Duration) {
+        println!(
+            "{days} days in {elapsed:.2?} ({busy:.2?} solving): {} parts solved, {} failed",
+            self.ok + self.passed + self.missing,
+            self.failures.len(),
+        );
+        if !self.failures.is_empty() {
+            println!("failed: {}", self.failures.join(", "));
+        }
+    }
+}
+
+/// Puts multi-line answers, like letters drawn in ASCII art, on their own lines.
+fn inline(answer: &str) -> String {
+    if answer.contains('\n') {
+        format!("\n{}\n", answer.trim_end())
+    } else {
+        format!(" {answer}")
+    }
+}
+
+/// Lines of `expected` missing from `answer` are marked with `-`, lines of `answer` that weren't
+/// expected with `+`.
+fn diff(expected: &str, answer: &str) -> String {
+    let expected: Vec<&str> = expected.trim_end().lines().collect();
+    let answer: Vec<&str> = answer.trim_end().lines().collect();
+    let mut diff = String::new();
+    for i in 0..expected.len().max(answer.len()) {
+        match (expected.get(i), answer.get(i)) {
+            (Some(e), Some(a)) if e == a => diff += &format!("  {e}\n"),
+            (e, a) => {
+                if let Some(e) = e {
+                    diff += &format!("- {e}\n");
+                }
+                if let Some(a) = a {
+                    diff += &format!("+ {a}\n");
+                }
+            }
+        }
+    }
+    diff.trim_end().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use aoc_common::{Answer, Solution};
+
+    use super::*;
+
+    struct Unused;
+
+    impl Solution for Unused {
+        fn part1(&self, _input: &str) -> eyre::Result<Answer> {
+            unreachable!()
+        }
+        fn part2(&self, _input: &str) -> eyre::Result<Answer> {
+            unreachable!()
+        }
+    }
+
+    const PUZZLE: Puzzle = Puzzle::new(2022, 10, &Unused);
+
+    #[test]
+    fn json() {
+        let record =
+            Record::solved(&PUZZLE, 2, "#.#\n.#.\n".to_string(), Some(None)).with_stats(Stats {
+                mean: Duration::from_micros(1500),
+                min: Duration::from_millis(1),
+                runs: 3,
+            });
+        assert_eq!(
+            serde_json::to_string(&record).unwrap(),
+            r##"{"year":2022,"day":10,"part":2,"status":"missing","answer":"#.#\n.#.\n","duration_ms":1.5,"min_duration_ms":1.0,"runs":3}"##
+        );
+
+        let record = Record::failed(&PUZZLE, 1, "no input".to_string());
+        assert_eq!(
+            serde_json::to_string(&record).unwrap(),
+            r#"{"year":2022,"day":10,"part":1,"status":"error","error":"no input"}"#
+        );
+    }
+
+    #[test]
+    fn status() {
+        let status = |expected: Option<Option<&str>>| {
+            let expected = expected.map(|e| e.map(str::to_string));
+            Record::solved(&PUZZLE, 1, "42".to_string(), expected).status
+        };
+        assert_eq!(status(None), Status::Ok);
+        assert_eq!(status(Some(Some("42"))), Status::Pass);
+        assert_eq!(status(Some(Some("41"))), Status::Fail);
+        assert_eq!(status(Some(None)), Status::Missing);
+    }
+
+    #[test]
+    fn diffs() {
+        assert_eq!(
+            diff("#..\n.#.\n..#\n", "#..\n##.\n"),
+            "  #..\n- .#.\n+ ##.\n- ..#"
+        );
+        let record = Record::solved(&PUZZLE, 1, "5".to_string(), Some(Some("4".to_string())))
+            .with_example("larger");
+        assert_eq!(
+            serde_json::to_string(&record).unwrap(),
+            r#"{"year":2022,"day":10,"part":1,"example":"larger","status":"fail","answer":"5","expected":"4"}"#
+        );
+    }
+}
@@ -0,0 +1,294 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use eyre::{ensure, eyre, Context, Result};
+
+const DAY: &str = include_str!("../templates/day.rs.tmpl");
+const CARGO_TOML: &str = include_str!("../templates/Cargo.toml.tmpl");
+const LIB: &str = include_str!("../templates/lib.rs.tmpl");
+const MAIN: &str = include_str!("../templates/main.rs.tmpl");
+
+const REGISTRY: &str = "pub const PUZZLES: &[Puzzle] = &[";
+
+/// Creates `crates/{year}/src/day{day}.rs` and registers it in the year's `lib.rs` and
+/// `main.rs`, creating the year crate first if it doesn't exist yet. Also creates empty
+/// `example.txt`, `example.part1` and `example.part2` files in `examples/{year}/day{day}` to
+/// paste the puzzle's example into, keeping any that exist already. Returns the created or
+/// changed files.
+pub fn new_day(root: &Path, year: u32, day: u32) -> Result<Vec<PathBuf>> {
+    ensure!((1..=25).contains(&day), "there is no day {day}");
+    let mut changed = Vec::new();
+    let crate_dir = root.join("crates").join(year.to_string());
+    if !crate_dir.exists() {
+        changed.extend(new_year(root, year)?);
+    }
+
+    let src = crate_dir.join("src");
+    let day_file = src.join(format!("day{day}.rs"));
+    ensure!(!day_file.exists(), "{} already exists", day_file.display());
+    let lib = src.join("lib.rs");
+    let main = src.join("main.rs");
+
+    let lib_text = read(&lib)?;
+    // Older years panic instead of returning an error when the input is missing.
+    let input = if lib_text.contains("-> Result<String, InputError>") {
+        format!("input({day})?")
+    } else {
+        format!("input({day})")
+    };
+    let registered = register(&lib_text, year, day)?;
+    let main_text = add_println(&read(&main)?, day);
+
+    write(
+        &day_file,
+        &fill(DAY, year, day).replace("{{input}}", &input),
+    )?;
+    write(&lib, &registered)?;
+    write(&main, &main_text)?;
+    changed.extend([day_file, lib, main]);
+
+    let examples = root
+        .join("examples")
+        .join(year.to_string())
+        .join(format!("day{day}"));
+    fs::create_dir_all(&examples)
+        .wrap_err_with(|| format!("failed to create {}", examples.display()))?;
+    for name in ["example.txt", "example.part1", "example.part2"] {
+        let path = examples.join(name);
+        if !path.exists() {
+            write(&path, "")?;
+            changed.push(path);
+        }
+    }
+    Ok(changed)
+}
+
+/// Creates a crate for `year` and adds it to the runner.
+fn new_year(root: &Path, year: u32) -> Result<Vec<PathBuf>> {
+    let crate_dir = root.join("crates").join(year.to_string());
+    let cargo_toml = crate_dir.join("Cargo.toml");
+    let lib = crate_dir.join("src/lib.rs");
+    let main = crate_dir.join("src/main.rs");
+    let runner_toml = root.join("crates/aoc/Cargo.toml");
+    let registry = root.join("crates/aoc/src/registry.rs");
+
+    let runner_toml_text = insert_after_last(
+        &read(&runner_toml)?,
+        |line| line.starts_with("aoc") && line.contains("{ path = \"../"),
+        &format!("aoc{year} = {{ path = \"../{year}\" }}"),
+    )
+    .ok_or_else(|| eyre!("no year crate dependency in {}", runner_toml.display()))?;
+    let registry_text = insert_after_last(
+        &read(&registry)?,
+        |line| line.trim_start().starts_with("aoc") && line.ends_with("::PUZZLES,"),
+        &format!("    aoc{year}::PUZZLES,"),
+    )
+    .ok_or_else(|| eyre!("no year registry in {}", registry.display()))?;
+
+    fs::create_dir_all(crate_dir.join("src"))?;
+    write(&cargo_toml, &fill(CARGO_TOML, year, 0))?;
+    write(&lib, &fill(LIB, year, 0))?;
+    write(&main, &fill(MAIN, year, 0))?;
+    write(&runner_toml, &runner_toml_text)?;
+    write(&registry, &registry_text)?;
+    Ok(vec![cargo_toml, runner_toml, registry])
+}
+
+fn fill(template: &str, year: u32, day: u32) -> String {
+    template
+        .replace("{{year}}", &year.to_string())
+        .replace("{{day}}", &day.to_string())
+}
+
+/// Adds `pub mod day{day};` to the module declarations and the day to `PUZZLES`, both kept
+/// in the order rustfmt would put them.
+fn register(lib: &str, year: u32, day: u32) -> Result<String> {
+    let start = lib
+        .find(REGISTRY)
+        .ok_or_else(|| eyre!("lib.rs has no `PUZZLES` registry"))?;
+    let end = lib[start..]
+        .find("];")
+        .map(|end| start + end + 2)
+        .ok_or_else(|| eyre!("`PUZZLES` registry isn't closed"))?;
+    let mut days = lib[start..end]
+        .split("Puzzle::new(")
+        .skip(1)
+        .map(|entry| {
+            entry
+                .split(',')
+                .nth(1)
+                .and_then(|day| day.trim().parse::<u32>().ok())
+                .ok_or_else(|| eyre!("unexpected registry entry `{entry}`"))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    ensure!(!days.contains(&day), "day {day} is registered already");
+    days.push(day);
+    days.sort();
+
+    let entry = |day: u32| format!("Puzzle::new({year}, {day}, &day{day}::Day{day})");
+    let registry = match days[..] {
+        [day] => format!("{REGISTRY}{}];", entry(day)),
+        _ => {
+            let entries: String = days
+                .iter()
+                .map(|&day| format!("    {},\n", entry(day)))
+                .collect();
+            format!("{REGISTRY}\n{entries}];")
+        }
+    };
+    let lib = format!("{}{registry}{}", &lib[..start], &lib[end..]);
+
+    let mut lines: Vec<&str> = lib.lines().collect();
+    let module = format!("pub mod day{day};");
+    let mods: Vec<usize> = (0..lines.len())
+        .filter(|&i| lines[i].starts_with("pub mod day"))
+        .collect();
+    match (mods.first(), mods.last()) {
+        (Some(&first), Some(&last)) => {
+            lines.insert(last + 1, &module);
+            lines[first..=last + 1]
+                .sort_by_key(|line| line.trim_start_matches("pub mod ").trim_end_matches(';'));
+        }
+        _ => {
+            let at = lines
+                .iter()
+                .position(|line| line.starts_with(REGISTRY))
+                .expect("registry was just written");
+            lines.splice(at..at, [module.as_str(), ""]);
+        }
+    }
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Prints the new day in the year's `main`, copying the style of the last day printed there.
+fn add_println(main: &str, day: u32) -> String {
+    fn printed_day(line: &str) -> Option<&str> {
+        let rest = line.trim().strip_prefix("println!(\"day")?;
+        let (number, rest) = rest.split_once(": {:?}\", ")?;
+        rest.contains("::solve()").then_some(number)
+    }
+    let line = main
+        .lines()
+        .rev()
+        .find_map(|line| Some((line, printed_day(line)?)))
+        .map(|(line, last)| line.replace(&format!("day{last}"), &format!("day{day}")))
+        .unwrap_or_else(|| format!("    println!(\"day{day}: {{:?}}\", day{day}::solve());"));
+
+    let mut lines: Vec<&str> = main.lines().collect();
+    let end = lines
+        .iter()
+        .rposition(|line| *line == "}")
+        .unwrap_or(lines.len());
+    lines.insert(end, &line);
+    lines.join("\n") + "\n"
+}
+
+fn insert_after_last(text: &str, matches: impl Fn(&str) -> bool, new: &str) -> Option<String> {
+    let mut lines: Vec<&str> = text.lines().collect();
+    let last = lines.iter().rposition(|line| matches(line))?;
+    lines.insert(last + 1, new);
+    Some(lines.join("\n") + "\n")
+}
+
+fn read(path: &Path) -> Result<String> {
+    fs::read_to_string(path).wrap_err_with(|| format!("failed to read {}", path.display()))
+}
+
+fn write(path: &Path, text: &str) -> Result<()> {
+    fs::write(path, text).wrap_err_with(|| format!("failed to write {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registers_days() {
+        let lib = "use aoc_common::Puzzle;\n\npub mod day1;\n\npub const PUZZLES: &[Puzzle] = &[Puzzle::new(2024, 1, &day1::Day1)];\n\npub fn input() {}\n";
+        let lib = register(lib, 2024, 10).unwrap();
+        let lib = register(&lib, 2024, 2).unwrap();
+        assert_eq!(
+            lib,
+            "\
+use aoc_common::Puzzle;
+
+pub mod day1;
+pub mod day10;
+pub mod day2;
+
+pub const PUZZLES: &[Puzzle] = &[
+    Puzzle::new(2024, 1, &day1::Day1),
+    Puzzle::new(2024, 2, &day2::Day2),
+    Puzzle::new(2024, 10, &day10::Day10),
+];
+
+pub fn input() {}
+"
+        );
+        assert!(register(&lib, 2024, 2).is_err());
+
+        let empty = fill(LIB, 2030, 0);
+        assert!(register(&empty, 2030, 1)
+            .unwrap()
+            .contains("pub mod day1;\n\npub const PUZZLES: &[Puzzle] = &[Puzzle::new(2030, 1, &day1::Day1)];\n"));
+    }
+
+    #[test]
+    fn prints_days() {
+        let main = "fn main() {\n    aoc22::init();\n    println!(\"day12: {:?}\", aoc22::day12::solve());\n}\n";
+        assert_eq!(
+            add_println(main, 13),
+            "fn main() {\n    aoc22::init();\n    println!(\"day12: {:?}\", aoc22::day12::solve());\n    println!(\"day13: {:?}\", aoc22::day13::solve());\n}\n"
+        );
+        assert!(add_println(&fill(MAIN, 2030, 0), 1)
+            .ends_with("    println!(\"day1: {:?}\", day1::solve());\n}\n"));
+    }
+
+    #[test]
+    fn creates_year_crates() {
+        let root = std::env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        fs::create_dir_all(root.join("crates/aoc/src")).unwrap();
+        fs::write(
+            root.join("crates/aoc/Cargo.toml"),
+            "[dependencies]\naoc-common = { path = \"../common\" }\naoc2024 = { path = \"../2024\" }\nclap.workspace = true\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("crates/aoc/src/registry.rs"),
+            "const YEARS: &[&[Puzzle]] = &[\n    aoc2024::PUZZLES,\n];\n",
+        )
+        .unwrap();
+
+        fs::create_dir_all(root.join("examples/2025/day1")).unwrap();
+        fs::write(root.join("examples/2025/day1/example.txt"), "1 2 3\n").unwrap();
+
+        let created = new_day(&root, 2025, 1).unwrap();
+        assert!(!created.contains(&root.join("examples/2025/day1/example.txt")));
+        assert!(created.contains(&root.join("examples/2025/day1/example.part1")));
+        new_day(&root, 2025, 2).unwrap();
+
+        let read = |path: &str| fs::read_to_string(root.join(path)).unwrap();
+        assert!(read("crates/aoc/Cargo.toml")
+            .contains("aoc2024 = { path = \"../2024\" }\naoc2025 = { path = \"../2025\" }\n"));
+        assert!(read("crates/aoc/src/registry.rs").contains("    aoc2025::PUZZLES,\n];"));
+        assert!(read("crates/2025/Cargo.toml").contains("name = \"aoc2025\""));
+        assert!(read("crates/2025/src/lib.rs").contains("Puzzle::new(2025, 2, &day2::Day2),"));
+        assert!(read("crates/2025/src/main.rs").contains("day2::solve()"));
+        let day = read("crates/2025/src/day2.rs");
+        assert!(day.contains("pub struct Day2;") && day.contains("input(2)?"));
+        assert!(!day.contains("#[cfg(test)]"));
+        assert_eq!(read("examples/2025/day1/example.txt"), "1 2 3\n");
+        assert_eq!(read("examples/2025/day2/example.txt"), "");
+        assert_eq!(read("examples/2025/day2/example.part1"), "");
+        assert_eq!(read("examples/2025/day2/example.part2"), "");
+        // `aoc --examples` skips the new day until its answers are filled in.
+        let examples = crate::examples::load(&root.join("examples"), 2025, 2).unwrap();
+        assert_eq!(examples.len(), 1);
+        assert_eq!(examples[0].expected, [None, None]);
+        assert!(new_day(&root, 2025, 2).is_err());
+
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,92 @@
+use std::{ops::RangeInclusive, str::FromStr};
+
+/// Years, days or parts picked on the command line.
+///
+/// Accepts a single number (`15`), a range (`1..=10` or `1..10`), a comma separated
+/// list of those (`1,3,5..=7`) or `all`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Selection {
+    All,
+    Ranges(Vec<RangeInclusive<u32>>),
+}
+
+impl Selection {
+    pub fn contains(&self, n: u32) -> bool {
+        match self {
+            Selection::All => true,
+            Selection::Ranges(ranges) => ranges.iter().any(|range| range.contains(&n)),
+        }
+    }
+}
+
+impl FromStr for Selection {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.trim() == "all" {
+            return Ok(Selection::All);
+        }
+        s.split(',')
+            .map(parse_range)
+            .collect::<Result<_, _>>()
+            .map(Selection::Ranges)
+    }
+}
+
+fn parse_range(s: &str) -> Result<RangeInclusive<u32>, String> {
+    let s = s.trim();
+    let num = |n: &str| {
+        n.trim()
+            .parse::<u32>()
+            .map_err(|e| format!("invalid number {n:?} in {s:?}: {e}"))
+    };
+    let range = if let Some((from, to)) = s.split_once("..=") {
+        num(from)?..=num(to)?
+    } else if let Some((from, to)) = s.split_once("..") {
+        let to = num(to)?
+            .checked_sub(1)
+            .ok_or_else(|| format!("empty range {s:?}"))?;
+        num(from)?..=to
+    } else {
+        let n = num(s)?;
+        n..=n
+    };
+    if range.is_empty() {
+        return Err(format!("empty range {s:?}"));
+    }
+    Ok(range)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses() {
+        assert_eq!("all".parse(), Ok(Selection::All));
+        assert_eq!("15".parse(), Ok(Selection::Ranges(vec![15..=15])));
+        assert_eq!("1..=10".parse(), Ok(Selection::Ranges(vec![1..=10])));
+        assert_eq!("1..10".parse(), Ok(Selection::Ranges(vec![1..=9])));
+        assert_eq!(
+            "1,3,5..=7".parse(),
+            Ok(Selection::Ranges(vec![1..=1, 3..=3, 5..=7]))
+        );
+        assert!("1..0".parse::<Selection>().is_err());
+        assert!("3..3".parse::<Selection>().is_err());
+        assert_eq!(
+            "5..3".parse::<Selection>(),
+            Err(r#"empty range "5..3""#.to_string())
+        );
+        assert!("5..=3".parse::<Selection>().is_err());
+        assert!("one".parse::<Selection>().is_err());
+    }
+
+    #[test]
+    fn contains() {
+        let selection: Selection = "1..=3,7".parse().unwrap();
+        assert!(selection.contains(2));
+        assert!(selection.contains(7));
+        assert!(!selection.contains(5));
+        assert!(Selection::All.contains(25));
+    }
+}
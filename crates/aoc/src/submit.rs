@@ -0,0 +1,357 @@
+//! Understands the replies to submitted answers and remembers every attempt, so a wrong answer
+//! is never sent twice and the site's waiting times are respected.
+
+use std::{
+    fmt,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use eyre::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+
+/// `submissions.jsonl` in the workspace root.
+pub fn default_history() -> PathBuf {
+    crate::workspace_root().join("submissions.jsonl")
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint in which direction.
+    Wrong,
+    /// The previous answer was submitted too recently, this one wasn't checked.
+    TooSoon,
+    /// The part is solved already or still locked, the answer wasn't checked.
+    NotChecked,
+}
+
+impl Verdict {
+    fn is_wrong(self) -> bool {
+        matches!(self, Verdict::TooHigh | Verdict::TooLow | Verdict::Wrong)
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Verdict::Correct => "is correct",
+            Verdict::TooHigh => "is too high",
+            Verdict::TooLow => "is too low",
+            Verdict::Wrong => "is wrong",
+            Verdict::TooSoon => "was not checked, the last answer was too recent",
+            Verdict::NotChecked => "was not checked, the part is solved already or still locked",
+        })
+    }
+}
+
+#[derive(PartialEq, Eq, Debug)]
+pub struct Response {
+    pub verdict: Verdict,
+    /// How long the site wants us to wait before the next answer.
+    pub wait: Option<Duration>,
+}
+
+impl Response {
+    pub fn parse(page: &str) -> Result<Self> {
+        let text = article(page);
+        let verdict = if text.contains("That's the right answer") {
+            Verdict::Correct
+        } else if text.contains("your answer is too high") {
+            Verdict::TooHigh
+        } else if text.contains("your answer is too low") {
+            Verdict::TooLow
+        } else if text.contains("That's not the right answer") {
+            Verdict::Wrong
+        } else if text.contains("You gave an answer too recently") {
+            Verdict::TooSoon
+        } else if text.contains("You don't seem to be solving the right level") {
+            Verdict::NotChecked
+        } else {
+            bail!("unexpected response: {}", text.trim());
+        };
+        Ok(Response {
+            verdict,
+            wait: wait_time(&text),
+        })
+    }
+}
+
+/// The text of the page's `<article>`, which holds the reply, without tags.
+fn article(page: &str) -> String {
+    let article = page
+        .split_once("<article")
+        .and_then(|(_, rest)| rest.split_once('>'))
+        .and_then(|(_, rest)| rest.split_once("</article>"))
+        .map_or(page, |(article, _)| article);
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in article.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text
+}
+
+/// Understands "You have 1m 20s left to wait" and "please wait one minute before trying again".
+fn wait_time(text: &str) -> Option<Duration> {
+    if let Some((_, rest)) = text.split_once("You have ") {
+        let (left, _) = rest.split_once(" left to wait")?;
+        return left
+            .split_whitespace()
+            .try_fold(Duration::ZERO, |wait, amount| {
+                let seconds = match amount.strip_suffix('m') {
+                    Some(minutes) => minutes.parse::<u64>().ok()? * 60,
+                    None => amount.strip_suffix('s')?.parse().ok()?,
+                };
+                Some(wait + Duration::from_secs(seconds))
+            });
+    }
+    let (_, rest) = text.split_once("please wait ")?;
+    let (wait, _) = rest.split_once(" before trying again")?;
+    let (amount, unit) = wait.split_once(' ')?;
+    let amount = match amount {
+        "a" | "one" => 1,
+        amount => amount.parse().ok()?,
+    };
+    let unit = match unit.trim_end_matches('s') {
+        "second" => 1,
+        "minute" => 60,
+        "hour" => 60 * 60,
+        _ => return None,
+    };
+    Some(Duration::from_secs(amount * unit))
+}
+
+/// One submitted answer and the site's reply.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct Attempt {
+    pub year: u32,
+    pub day: u32,
+    pub part: u32,
+    pub answer: String,
+    pub verdict: Verdict,
+    /// Seconds since the Unix epoch.
+    pub at: u64,
+    /// Seconds to wait before the next answer.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wait: Option<u64>,
+}
+
+impl Attempt {
+    pub fn new(year: u32, day: u32, part: u32, answer: &str, response: &Response) -> Self {
+        Attempt {
+            year,
+            day,
+            part,
+            answer: answer.to_string(),
+            verdict: response.verdict,
+            at: now(),
+            wait: response.wait.map(|wait| wait.as_secs()),
+        }
+    }
+}
+
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("the clock is after 1970")
+        .as_secs()
+}
+
+/// Every attempt so far, one JSON object per line.
+pub struct History {
+    path: PathBuf,
+    attempts: Vec<Attempt>,
+}
+
+impl History {
+    pub fn load(path: &Path) -> Result<Self> {
+        let attempts = match fs::read_to_string(path) {
+            Ok(text) => text
+                .lines()
+                .enumerate()
+                .filter(|(_, line)| !line.trim().is_empty())
+                .map(|(i, line)| {
+                    serde_json::from_str(line)
+                        .wrap_err_with(|| format!("{}:{}: invalid attempt", path.display(), i + 1))
+                })
+                .collect::<Result<_>>()?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(e).wrap_err_with(|| format!("failed to read {}", path.display())),
+        };
+        Ok(History {
+            path: path.to_path_buf(),
+            attempts,
+        })
+    }
+
+    /// Fails if submitting `answer` at `now` is known to be pointless: it was tried before, it
+    /// is past an answer that was too high or too low, the part is solved, or the site asked to
+    /// wait longer.
+    pub fn check(&self, year: u32, day: u32, part: u32, answer: &str, now: u64) -> Result<()> {
+        let day_attempts = self
+            .attempts
+            .iter()
+            .filter(|attempt| attempt.year == year && attempt.day == day);
+        if let Some(until) = day_attempts
+            .filter_map(|attempt| Some(attempt.at + attempt.wait?))
+            .max()
+            .filter(|&until| until > now)
+        {
+            bail!("the site asked to wait {}s more", until - now);
+        }
+
+        let number = answer.parse::<i128>().ok();
+        for attempt in self
+            .attempts
+            .iter()
+            .filter(|a| a.year == year && a.day == day && a.part == part)
+        {
+            if attempt.verdict == Verdict::Correct {
+                bail!("already solved with {}", attempt.answer);
+            }
+            if attempt.verdict.is_wrong() && attempt.answer == answer {
+                bail!("{answer} was submitted before and {}", attempt.verdict);
+            }
+            let (Some(number), Ok(tried)) = (number, attempt.answer.parse::<i128>()) else {
+                continue;
+            };
+            match attempt.verdict {
+                Verdict::TooHigh if number >= tried => {
+                    bail!("{answer} is too high, {tried} already was")
+                }
+                Verdict::TooLow if number <= tried => {
+                    bail!("{answer} is too low, {tried} already was")
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    pub fn record(&mut self, attempt: Attempt) -> Result<()> {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .wrap_err_with(|| format!("failed to open {}", self.path.display()))?;
+        writeln!(file, "{}", serde_json::to_string(&attempt)?)
+            .wrap_err_with(|| format!("failed to write {}", self.path.display()))?;
+        self.attempts.push(attempt);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn page(article: &str) -> String {
+        format!("<html><body><main>\n<article><p>{article}</p></article>\n</main></body></html>")
+    }
+
+    #[test]
+    fn responses() {
+        let response = |article| Response::parse(&page(article)).unwrap();
+        assert_eq!(
+            response("That's the right answer!  You are <span class=\"day-success\">one gold star</span> closer."),
+            Response { verdict: Verdict::Correct, wait: None }
+        );
+        assert_eq!(
+            response("That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data; please wait one minute before trying again. <a href=\"/2022/day/1\">[Return to Day 1]</a>"),
+            Response { verdict: Verdict::TooHigh, wait: Some(Duration::from_secs(60)) }
+        );
+        assert_eq!(
+            response("That's not the right answer; your answer is too low.  Because you have guessed incorrectly 5 times on this puzzle, please wait 5 minutes before trying again."),
+            Response { verdict: Verdict::TooLow, wait: Some(Duration::from_secs(300)) }
+        );
+        assert_eq!(
+            response("That's not the right answer.  If you're stuck, make sure you're using the full input data."),
+            Response { verdict: Verdict::Wrong, wait: None }
+        );
+        assert_eq!(
+            response("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 20s left to wait."),
+            Response { verdict: Verdict::TooSoon, wait: Some(Duration::from_secs(80)) }
+        );
+        assert_eq!(
+            response("You don't seem to be solving the right level.  Did you already complete it?"),
+            Response {
+                verdict: Verdict::NotChecked,
+                wait: None
+            }
+        );
+        let error = Response::parse(&page("Puzzle inputs differ by user.")).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "unexpected response: Puzzle inputs differ by user."
+        );
+    }
+
+    #[test]
+    fn checks() {
+        let dir = std::env::temp_dir().join(format!("aoc-submit-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("submissions.jsonl");
+        let mut history = History::load(&path).unwrap();
+        let attempt = |answer: &str, verdict, at, wait| Attempt {
+            year: 2022,
+            day: 1,
+            part: 1,
+            answer: answer.to_string(),
+            verdict,
+            at,
+            wait,
+        };
+        history
+            .record(attempt("100", Verdict::TooHigh, 1000, Some(60)))
+            .unwrap();
+        history
+            .record(attempt("10", Verdict::TooLow, 1100, None))
+            .unwrap();
+        history
+            .record(attempt("ABC", Verdict::Wrong, 1200, None))
+            .unwrap();
+
+        let history = History::load(&path).unwrap();
+        let check = |part, answer, now| {
+            history
+                .check(2022, 1, part, answer, now)
+                .map_err(|e| e.to_string())
+        };
+        assert_eq!(
+            check(1, "50", 1030),
+            Err("the site asked to wait 30s more".into())
+        );
+        assert_eq!(check(1, "50", 1060), Ok(()));
+        assert_eq!(check(2, "100", 1060), Ok(()));
+        assert_eq!(check(1, "XYZ", 2000), Ok(()));
+        assert_eq!(
+            check(1, "ABC", 2000),
+            Err("ABC was submitted before and is wrong".into())
+        );
+        assert_eq!(
+            check(1, "100", 2000),
+            Err("100 was submitted before and is too high".into())
+        );
+        assert_eq!(
+            check(1, "150", 2000),
+            Err("150 is too high, 100 already was".into())
+        );
+        assert_eq!(
+            check(1, "9", 2000),
+            Err("9 is too low, 10 already was".into())
+        );
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,158 @@
+use std::time::{Duration, Instant};
+
+use aoc_common::Puzzle;
+use eyre::Result;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Stats {
+    pub mean: Duration,
+    pub min: Duration,
+    pub runs: u32,
+}
+
+impl Stats {
+    fn new(times: &[Duration]) -> Self {
+        let runs = times.len() as u32;
+        Stats {
+            mean: times.iter().sum::<Duration>() / runs,
+            min: times.iter().copied().min().unwrap_or_default(),
+            runs,
+        }
+    }
+}
+
+/// Calls `f` `runs` times and returns the result of the first call with the times of all of
+/// them. The first error of any call is returned instead, without times.
+pub fn measure<T>(runs: u32, mut f: impl FnMut() -> Result<T>) -> Result<(T, Stats)> {
+    let start = Instant::now();
+    let first = f()?;
+    let mut times = vec![start.elapsed()];
+    for _ in 1..runs {
+        let start = Instant::now();
+        f()?;
+        times.push(start.elapsed());
+    }
+    Ok((first, Stats::new(&times)))
+}
+
+/// Timings of one day. Parts parse the input themselves, so their times include parsing, and
+/// `parse` only shows how much of them it takes.
+pub struct Row {
+    pub label: String,
+    pub parse: Option<Stats>,
+    pub parts: [Option<Stats>; 2],
+}
+
+impl Row {
+    pub fn new(puzzle: &Puzzle) -> Self {
+        Row {
+            label: format!("{} day{}", puzzle.year, puzzle.day),
+            parse: None,
+            parts: [None; 2],
+        }
+    }
+
+    /// Both parts, leaving out `parse` as the parts already include it.
+    pub fn total(&self) -> Duration {
+        self.parts.iter().flatten().map(|stats| stats.mean).sum()
+    }
+}
+
+/// Prints the mean time of every stage, with the minimum if it ran more than once, and flags
+/// days taking longer than `budget`.
+pub fn print_table(rows: &[Row], budget: Option<Duration>) {
+    let cell = |stats: &Option<Stats>| match stats {
+        Some(stats) if stats.runs > 1 => format!("{:.2?} (min {:.2?})", stats.mean, stats.min),
+        Some(stats) => format!("{:.2?}", stats.mean),
+        None => "-".to_string(),
+    };
+    let mut lines = vec![[
+        "day".to_string(),
+        "parse".to_string(),
+        "part 1".to_string(),
+        "part 2".to_string(),
+        "total".to_string(),
+    ]];
+    for row in rows {
+        let over_budget = budget.is_some_and(|budget| row.total() > budget);
+        lines.push([
+            row.label.clone(),
+            cell(&row.parse),
+            cell(&row.parts[0]),
+            cell(&row.parts[1]),
+            format!("{:.2?}{}", row.total(), if over_budget { " !" } else { "" }),
+        ]);
+    }
+
+    let mut widths = [0; 5];
+    for line in &lines {
+        for (width, cell) in widths.iter_mut().zip(line) {
+            *width = (*width).max(cell.len());
+        }
+    }
+    for line in &lines {
+        let cells: Vec<String> = line
+            .iter()
+            .zip(widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .collect();
+        println!("{}", cells.join("  ").trim_end());
+    }
+
+    if rows.iter().any(|row| row.parse.is_some()) {
+        println!("part times include parsing, the total leaves out the parse column");
+    }
+
+    if let Some(budget) = budget {
+        let over: Vec<&str> = rows
+            .iter()
+            .filter(|row| row.total() > budget)
+            .map(|row| row.label.as_str())
+            .collect();
+        if !over.is_empty() {
+            println!("over the {budget:?} budget: {}", over.join(", "));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stats() {
+        let ms = Duration::from_millis;
+        let stats = Stats::new(&[ms(30), ms(10), ms(20)]);
+        assert_eq!(stats.mean, ms(20));
+        assert_eq!(stats.min, ms(10));
+        assert_eq!(stats.runs, 3);
+    }
+
+    #[test]
+    fn total_leaves_out_parsing() {
+        let ms = Duration::from_millis;
+        let stats = |time| Some(Stats::new(&[time]));
+        let row = Row {
+            label: "2021 day1".to_string(),
+            parse: stats(ms(5)),
+            parts: [stats(ms(10)), stats(ms(20))],
+        };
+        assert_eq!(row.total(), ms(30));
+    }
+
+    #[test]
+    fn stops_at_first_error() {
+        let mut calls = 0;
+        let result = measure(5, || {
+            calls += 1;
+            eyre::ensure!(calls < 2, "run {calls} failed");
+            Ok(calls)
+        });
+        assert_eq!(result.unwrap_err().to_string(), "run 2 failed");
+        assert_eq!(calls, 2);
+
+        let (first, stats) = measure(3, || Ok(calls)).unwrap();
+        assert_eq!(first, 2);
+        assert_eq!(stats.runs, 3);
+    }
+}
@@ -0,0 +1,104 @@
+//! Re-runs one day whenever its solution, input or examples change.
+
+use std::{
+    path::{Path, PathBuf},
+    sync::mpsc,
+    time::Duration,
+};
+
+use eyre::Result;
+use notify::{RecursiveMode, Watcher};
+
+/// Editors save in several steps, so changes are collected until none came for this long.
+const SETTLE: Duration = Duration::from_millis(200);
+
+/// The files that make up a day.
+pub struct Watched {
+    source: PathBuf,
+    input: PathBuf,
+    examples: PathBuf,
+}
+
+impl Watched {
+    pub fn new(root: &Path, input_dir: &Path, examples_dir: &Path, year: u32, day: u32) -> Self {
+        Watched {
+            source: root
+                .join("crates")
+                .join(year.to_string())
+                .join("src")
+                .join(format!("day{day}.rs")),
+            input: aoc_common::input::input_path(input_dir, year, day),
+            examples: examples_dir
+                .join(year.to_string())
+                .join(format!("day{day}")),
+        }
+    }
+
+    /// Directories to watch for changes. Files are watched through their directory, as editors
+    /// often replace files instead of writing to them, and the day's examples may not exist yet.
+    fn dirs(&self) -> [(&Path, RecursiveMode); 3] {
+        fn parent(path: &Path) -> &Path {
+            path.parent().expect("watched files are in a directory")
+        }
+        [
+            (parent(&self.source), RecursiveMode::NonRecursive),
+            (parent(&self.input), RecursiveMode::NonRecursive),
+            (parent(&self.examples), RecursiveMode::Recursive),
+        ]
+    }
+
+    fn contains(&self, path: &Path) -> bool {
+        path == self.source || path == self.input || path.starts_with(&self.examples)
+    }
+}
+
+/// Calls `run` once, and again after every change to the watched files. Only returns if
+/// watching fails.
+pub fn watch(watched: &Watched, mut run: impl FnMut()) -> Result<()> {
+    let (sender, changes) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(sender)?;
+    for (dir, mode) in watched.dirs() {
+        if dir.is_dir() {
+            watcher.watch(dir, mode)?;
+        } else {
+            eprintln!("not watching {}, it doesn't exist", dir.display());
+        }
+    }
+
+    run();
+    loop {
+        let event = changes.recv()??;
+        if event.kind.is_access() || !event.paths.iter().any(|path| watched.contains(path)) {
+            continue;
+        }
+        while changes.recv_timeout(SETTLE).is_ok() {}
+        run();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn watched_files() {
+        let root = Path::new("/aoc");
+        let watched = Watched::new(root, &root.join("inputs"), &root.join("examples"), 2022, 12);
+        assert!(watched.contains(&root.join("crates/2022/src/day12.rs")));
+        assert!(watched.contains(&root.join("inputs/2022/12")));
+        let examples = root.join("examples/2022/day12");
+        assert!(watched.contains(&examples.join("example.part1")));
+
+        assert!(!watched.contains(&root.join("crates/2022/src/day1.rs")));
+        assert!(!watched.contains(&root.join("inputs/2022/1")));
+        assert!(!watched.contains(&examples.with_file_name("day1").join("example.txt")));
+        assert_eq!(
+            watched.dirs().map(|(dir, _)| dir.to_path_buf()),
+            [
+                root.join("crates/2022/src"),
+                root.join("inputs/2022"),
+                root.join("examples/2022"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,51 @@
+//! `aoc audit-passwords` lists the entries that break a policy and fails if there are any.
+
+use std::process::Command;
+
+const EXAMPLE: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/../../examples/2020/day2/example.txt"
+);
+
+fn audit(policy: &str) -> (bool, String, String) {
+    let output = Command::new(env!("CARGO_BIN_EXE_aoc"))
+        .args(["audit-passwords", "--policy", policy, "--input", EXAMPLE])
+        .env("RUST_LOG", "off")
+        .output()
+        .unwrap();
+    (
+        output.status.success(),
+        String::from_utf8(output.stdout).unwrap(),
+        String::from_utf8(output.stderr).unwrap(),
+    )
+}
+
+#[test]
+fn lists_failures() {
+    let (success, stdout, stderr) = audit("positions");
+    assert!(!success);
+    assert!(
+        stdout.contains("line 2: 1-3 b: cdefg: 'b' is at neither position 1 nor 3\n"),
+        "{stdout}"
+    );
+    assert!(
+        stdout.contains("line 3: 2-9 c: ccccccccc: 'c' is at both positions 2 and 9\n"),
+        "{stdout}"
+    );
+    assert!(
+        stdout.contains("1 of 3 entries pass positions\n"),
+        "{stdout}"
+    );
+    assert!(stderr.contains("2 entries fail positions"), "{stderr}");
+
+    let (success, stdout, _) = audit("any(positions, not(positions))");
+    assert!(success);
+    assert!(stdout.contains("3 of 3 entries pass"), "{stdout}");
+}
+
+#[test]
+fn invalid_policy() {
+    let (success, _, stderr) = audit("nope");
+    assert!(!success);
+    assert!(stderr.contains("invalid policy"), "{stderr}");
+}
@@ -0,0 +1,156 @@
+//! Runs `aoc describe` against a local stand-in for adventofcode.com serving saved puzzle
+//! pages, then reads them with `aoc show`.
+
+use std::{
+    fs,
+    io::{BufRead, BufReader, Write},
+    net::TcpListener,
+    path::Path,
+    process::{Command, Output},
+    sync::{Arc, Mutex},
+    thread,
+};
+
+/// Answers `/2015/day/1` with the page before part 1 was solved, and with both parts from the
+/// second request on. Records the request line of every request.
+fn serve() -> (String, Arc<Mutex<Vec<String>>>) {
+    let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+    let pages = [
+        fs::read_to_string(fixtures.join("day1-part1.html")).unwrap(),
+        fs::read_to_string(fixtures.join("day1.html")).unwrap(),
+    ];
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    let recorded = requests.clone();
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut request = String::new();
+            let mut reader = BufReader::new(&stream);
+            reader.read_line(&mut request).unwrap();
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line.trim().is_empty() {
+                    break;
+                }
+            }
+            let mut recorded = recorded.lock().unwrap();
+            let response = if request.starts_with("GET /2015/day/1 ") {
+                let page = &pages[recorded.len().min(1)];
+                format!(
+                    "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{page}",
+                    page.len()
+                )
+            } else {
+                "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                    .to_string()
+            };
+            recorded.push(request.trim().to_string());
+            stream.write_all(response.as_bytes()).unwrap();
+        }
+    });
+    (base_url, requests)
+}
+
+fn aoc(args: &[&str], dir: &Path) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_aoc"))
+        .args(args)
+        .arg("--cache-dir")
+        .arg(dir)
+        .env("AOC_SESSION", "53616c7465645f5f")
+        .output()
+        .unwrap()
+}
+
+fn stdout(output: Output) -> String {
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "{stderr}");
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn caches_descriptions() {
+    let (base_url, requests) = serve();
+    let dir = std::env::temp_dir().join(format!("aoc-describe-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    let describe = [
+        "describe",
+        "--year",
+        "2015",
+        "--day",
+        "1",
+        "--interval",
+        "0",
+        "--base-url",
+        &base_url,
+    ];
+    let day = dir.join("2015/day1");
+
+    let output = stdout(aoc(&describe, &dir));
+    assert!(
+        output.contains("2015 day1: 1 part(s) and 1 example(s)"),
+        "{output}"
+    );
+    assert_eq!(
+        fs::read_to_string(day.join("example1.txt")).unwrap(),
+        "3\n4\n\n10\n\n1\n2\n"
+    );
+    assert!(!day.join("example2.txt").exists());
+    let error = aoc(
+        &["show", "--year", "2015", "--day", "1", "--part", "2"],
+        &dir,
+    );
+    assert!(!error.status.success());
+
+    let output = stdout(aoc(&describe, &dir));
+    assert!(
+        output.contains("2015 day1: 2 part(s) and 2 example(s)"),
+        "{output}"
+    );
+    assert_eq!(
+        fs::read_to_string(day.join("example2.txt")).unwrap(),
+        "3 4\n<10>\n"
+    );
+    let output = stdout(aoc(&describe, &dir));
+    assert!(
+        output.contains("2015 day1 is cached with both parts"),
+        "{output}"
+    );
+    assert_eq!(*requests.lock().unwrap(), ["GET /2015/day/1 HTTP/1.1"; 2]);
+
+    let part1 = stdout(aoc(
+        &["show", "--year", "2015", "--day", "1", "--part", "1"],
+        &dir,
+    ));
+    assert_eq!(
+        part1,
+        "## --- Day 1: Counting Sheep ---\n\n\
+        The shepherd keeps a list of *flocks*, one sheep count per line and a blank line \
+        between flocks:\n\n\
+        ```\n3\n4\n\n10\n\n1\n2\n```\n\n\
+        Find the flock with the most sheep. In the example, that is the second flock with \
+        `10` sheep.\n\n\
+        *How many sheep are in the largest flock?*\n"
+    );
+    let part2 = stdout(aoc(
+        &["show", "--year", "2015", "--day", "1", "--part", "2"],
+        &dir,
+    ));
+    assert_eq!(
+        part2,
+        "## --- Part Two ---\n\n\
+        Sheep wander off when they're bored. Flocks smaller than `5` & larger than `20` lose a \
+        sheep every night:\n\n\
+        - A flock of `3` is gone after `3` nights.\n\
+        - A flock of `10` stays as it is.\n\n\
+        ```\n3 4\n<10>\n```\n\n\
+        *How many sheep are left after a week?*\n"
+    );
+    let both = stdout(aoc(&["show", "--year", "2015", "--day", "1"], &dir));
+    assert_eq!(both, format!("{part1}\n{part2}"));
+    assert!(!both.contains("Your puzzle answer"));
+
+    fs::remove_dir_all(dir).unwrap();
+}
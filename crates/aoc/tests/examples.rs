@@ -0,0 +1,17 @@
+//! Runs every solution on the examples in `examples/`, so adding an example there is enough to
+//! have it checked by `cargo test`.
+
+use std::process::Command;
+
+#[test]
+fn examples() {
+    let output = Command::new(env!("CARGO_BIN_EXE_aoc"))
+        .arg("--examples")
+        .env("RUST_LOG", "off")
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "{stdout}{stderr}");
+    assert!(stdout.contains(" passed, 0 failed"), "{stdout}");
+}
@@ -0,0 +1,147 @@
+//! Runs `aoc fetch` against a local stand-in for adventofcode.com.
+
+use std::{
+    fs,
+    io::{BufRead, BufReader, Write},
+    net::TcpListener,
+    path::PathBuf,
+    process::Command,
+    sync::{Arc, Mutex},
+    thread,
+    time::{Duration, Instant},
+};
+
+const SESSION: &str = "53616c7465645f5fcafe";
+
+/// Answers `/{year}/day/{day}/input` with a made up input and everything else with 404,
+/// recording the head of every request.
+fn serve() -> (String, Arc<Mutex<Vec<String>>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    let recorded = requests.clone();
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut head = String::new();
+            let mut reader = BufReader::new(&stream);
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line.trim().is_empty() {
+                    break;
+                }
+                head.push_str(&line);
+            }
+            let path = head
+                .split_whitespace()
+                .nth(1)
+                .unwrap_or_default()
+                .to_string();
+            recorded.lock().unwrap().push(head);
+
+            let response = match path.split('/').collect::<Vec<_>>()[..] {
+                ["", "2015", "day", day, "input"] if day != "3" => {
+                    let body = format!("input of day {day}\n");
+                    format!(
+                        "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                        body.len()
+                    )
+                }
+                _ => "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                    .to_string(),
+            };
+            stream.write_all(response.as_bytes()).unwrap();
+        }
+    });
+    (base_url, requests)
+}
+
+fn input_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("aoc-fetch-{name}-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    dir
+}
+
+fn fetch(base_url: &str, dir: &PathBuf, args: &[&str]) -> std::process::Output {
+    Command::new(env!("CARGO_BIN_EXE_aoc"))
+        .args(["fetch", "--base-url", base_url, "--input-dir"])
+        .arg(dir)
+        .args(args)
+        .env("AOC_SESSION", SESSION)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn downloads_missing_inputs() {
+    let (base_url, requests) = serve();
+    let dir = input_dir("missing");
+    fs::create_dir_all(dir.join("2015")).unwrap();
+    fs::write(dir.join("2015/2"), "already there\n").unwrap();
+
+    let output = fetch(
+        &base_url,
+        &dir,
+        &["--year", "2015", "--day", "1..=3", "--interval", "0"],
+    );
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+
+    assert!(!output.status.success(), "{stderr}");
+    assert_eq!(
+        fs::read_to_string(dir.join("2015/1")).unwrap(),
+        "input of day 1\n"
+    );
+    assert_eq!(
+        fs::read_to_string(dir.join("2015/2")).unwrap(),
+        "already there\n"
+    );
+    assert!(!dir.join("2015/3").exists());
+    assert!(stderr.contains("2015 day3 failed"), "{stderr}");
+    assert!(!stdout.contains(SESSION) && !stderr.contains(SESSION));
+
+    let requests = requests.lock().unwrap();
+    assert_eq!(requests.len(), 2);
+    assert!(requests[0].starts_with("GET /2015/day/1/input "));
+    assert!(requests[1].starts_with("GET /2015/day/3/input "));
+    for request in requests.iter() {
+        let request = request.to_lowercase();
+        assert!(request.contains(&format!("cookie: session={SESSION}")));
+        assert!(request.contains("user-agent: github.com/timofreiberg/adventofcode"));
+    }
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn waits_between_requests() {
+    let (base_url, requests) = serve();
+    let dir = input_dir("interval");
+
+    let start = Instant::now();
+    let output = fetch(
+        &base_url,
+        &dir,
+        &["--year", "2015", "--day", "1,2", "--interval", "300"],
+    );
+
+    assert!(output.status.success());
+    assert!(start.elapsed() >= Duration::from_millis(300));
+    assert_eq!(requests.lock().unwrap().len(), 2);
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn skips_locked_days() {
+    let (base_url, requests) = serve();
+    let dir = input_dir("locked");
+
+    let output = fetch(&base_url, &dir, &["--year", "2999"]);
+
+    assert!(output.status.success());
+    assert!(String::from_utf8(output.stdout)
+        .unwrap()
+        .contains("2999 day1 is not unlocked yet"));
+    assert!(requests.lock().unwrap().is_empty());
+    assert!(!dir.exists());
+}
@@ -0,0 +1,40 @@
+//! Days with an input compiled in only use it when the input directory has none for them.
+
+use std::{fs, process::Command};
+
+fn aoc(args: &[&str]) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_aoc"))
+        .args(["--year", "2021", "--day", "11", "--part", "1"])
+        .args(args)
+        .env("RUST_LOG", "off")
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn explicit_inputs_win() {
+    let example = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/../../examples/2021/day11/example.txt"
+    );
+    assert!(aoc(&["--input", example]).contains("2021 day11.1: 1656\n"));
+
+    let dir = std::env::temp_dir().join(format!("aoc-inputs-{}", std::process::id()));
+    fs::create_dir_all(dir.join("2021")).unwrap();
+    fs::copy(example, dir.join("2021/11")).unwrap();
+    let from_dir = aoc(&["--input-dir", dir.to_str().unwrap()]);
+    fs::remove_dir_all(&dir).unwrap();
+    assert!(from_dir.contains("2021 day11.1: 1656\n"), "{from_dir}");
+
+    // Without a file for the day, the compiled in input is used.
+    let empty = std::env::temp_dir().join(format!("aoc-no-inputs-{}", std::process::id()));
+    let embedded = aoc(&["--input-dir", empty.to_str().unwrap()]);
+    assert!(embedded.contains("2021 day11.1: "), "{embedded}");
+    assert!(!embedded.contains("2021 day11.1: 1656\n"), "{embedded}");
+}
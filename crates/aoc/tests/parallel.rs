@@ -0,0 +1,50 @@
+//! A day that panics or has no input must not keep the others from being solved and reported.
+
+use std::{fs, process::Command};
+
+#[test]
+fn broken_days_are_isolated() {
+    let dir = std::env::temp_dir().join(format!("aoc-parallel-{}", std::process::id()));
+    fs::create_dir_all(dir.join("2022")).unwrap();
+    fs::write(dir.join("2022/1"), "1000\n2000\n\n4000\n\n5000\n6000\n").unwrap();
+    // So little of the disk is used that part 2, which doesn't expect that, underflows.
+    fs::write(dir.join("2022/7"), "$ ls\n1 f\n").unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_aoc"))
+        .args([
+            "--year",
+            "2022",
+            "--day",
+            "1,2,7",
+            "--parallel",
+            "--input-dir",
+        ])
+        .arg(&dir)
+        .env("RUST_LOG", "off")
+        .output()
+        .unwrap();
+    fs::remove_dir_all(&dir).unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    assert!(!output.status.success());
+    assert!(
+        stdout.contains("2022 day1.1: 11000\n2022 day1.2: 18000\n"),
+        "{stdout}"
+    );
+    assert!(
+        stderr.contains("2022 day2.1 failed: no input file"),
+        "{stderr}"
+    );
+    assert!(stdout.contains("2022 day7.1: 1\n"), "{stdout}");
+    assert!(
+        stderr.contains("2022 day7.2 failed: panicked at"),
+        "{stderr}"
+    );
+    assert!(stdout.contains("3 days in "), "{stdout}");
+    assert!(stdout.contains("3 parts solved, 3 failed"), "{stdout}");
+    assert!(
+        stdout.contains("failed: 2022 day2.1, 2022 day2.2, 2022 day7.2"),
+        "{stdout}"
+    );
+}
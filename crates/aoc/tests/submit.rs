@@ -0,0 +1,169 @@
+//! Runs `aoc submit` against a local stand-in for adventofcode.com.
+
+use std::{
+    fs,
+    io::{BufRead, BufReader, Read, Write},
+    net::TcpListener,
+    path::{Path, PathBuf},
+    process::Command,
+    sync::{Arc, Mutex},
+    thread,
+};
+
+const SESSION: &str = "53616c7465645f5fcafe";
+
+/// Judges answers to `/2022/day/1/answer` like the site would if the right one was 24000 and
+/// anything starting with 9 came too soon, recording the form of every request.
+fn serve() -> (String, Arc<Mutex<Vec<String>>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    let recorded = requests.clone();
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut reader = BufReader::new(&stream);
+            let mut head = String::new();
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line.trim().is_empty() {
+                    break;
+                }
+                head.push_str(&line);
+            }
+            let length = head
+                .lines()
+                .find_map(|line| {
+                    line.to_lowercase()
+                        .strip_prefix("content-length: ")?
+                        .parse()
+                        .ok()
+                })
+                .unwrap_or(0);
+            let mut form = vec![0; length];
+            reader.read_exact(&mut form).unwrap();
+            let form = String::from_utf8(form).unwrap();
+            recorded.lock().unwrap().push(form.clone());
+
+            let answer = form
+                .split('&')
+                .find_map(|field| field.strip_prefix("answer="))
+                .unwrap_or_default();
+            let reply = match answer {
+                _ if !head.starts_with("POST /2022/day/1/answer ") => "Not found.",
+                "24000" => "That's the right answer!  You are <span class=\"day-success\">one gold star</span> closer.",
+                answer if answer.starts_with('9') => "You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 35s left to wait.",
+                answer if answer.parse::<u32>().unwrap() > 24000 => "That's not the right answer; your answer is too high.",
+                _ => "That's not the right answer; your answer is too low.",
+            };
+            let body = format!("<html><main><article><p>{reply}</p></article></main></html>");
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            stream.write_all(response.as_bytes()).unwrap();
+        }
+    });
+    (base_url, requests)
+}
+
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("aoc-submit-{name}-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("inputs/2022")).unwrap();
+    fs::write(
+        dir.join("inputs/2022/1"),
+        "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000\n",
+    )
+    .unwrap();
+    dir
+}
+
+fn submit(base_url: &str, dir: &Path, args: &[&str]) -> (bool, String) {
+    let output = Command::new(env!("CARGO_BIN_EXE_aoc"))
+        .args(["submit", "--year", "2022", "--day", "1", "--part", "1"])
+        .args(["--base-url", base_url])
+        .arg("--input-dir")
+        .arg(dir.join("inputs"))
+        .arg("--answers-dir")
+        .arg(dir.join("answers"))
+        .arg("--history")
+        .arg(dir.join("submissions.jsonl"))
+        .args(args)
+        .env("AOC_SESSION", SESSION)
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(!stdout.contains(SESSION) && !stderr.contains(SESSION));
+    (output.status.success(), stdout + &stderr)
+}
+
+#[test]
+fn wrong_answers_are_not_resubmitted() {
+    let (base_url, requests) = serve();
+    let dir = temp_dir("wrong");
+
+    let (ok, output) = submit(&base_url, &dir, &["--answer", "30000"]);
+    assert!(!ok);
+    assert!(
+        output.contains("2022 day1.1: 30000 is too high"),
+        "{output}"
+    );
+    let (ok, output) = submit(&base_url, &dir, &["--answer", "30000"]);
+    assert!(!ok);
+    assert!(
+        output.contains("was submitted before and is too high"),
+        "{output}"
+    );
+    let (ok, output) = submit(&base_url, &dir, &["--answer", "31000"]);
+    assert!(!ok);
+    assert!(
+        output.contains("31000 is too high, 30000 already was"),
+        "{output}"
+    );
+    assert_eq!(requests.lock().unwrap().len(), 1);
+
+    let (ok, output) = submit(&base_url, &dir, &[]);
+    assert!(ok, "{output}");
+    assert!(output.contains("2022 day1.1: 24000 is correct"), "{output}");
+    let (ok, output) = submit(&base_url, &dir, &[]);
+    assert!(ok, "{output}");
+    assert!(
+        output.contains("24000 is the confirmed answer already"),
+        "{output}"
+    );
+
+    assert_eq!(
+        *requests.lock().unwrap(),
+        ["level=1&answer=30000", "level=1&answer=24000"]
+    );
+    assert!(fs::read_to_string(dir.join("answers/2022.toml"))
+        .unwrap()
+        .contains("[day1]\npart1 = 24000\n"));
+    let history = fs::read_to_string(dir.join("submissions.jsonl")).unwrap();
+    assert_eq!(history.lines().count(), 2);
+    assert!(history.contains("\"verdict\":\"too_high\""), "{history}");
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn waits_when_told_to() {
+    let (base_url, requests) = serve();
+    let dir = temp_dir("wait");
+
+    let (ok, output) = submit(&base_url, &dir, &["--answer", "90000"]);
+    assert!(!ok);
+    assert!(
+        output.contains("90000 was not checked, the last answer was too recent, wait 35s"),
+        "{output}"
+    );
+    let (ok, output) = submit(&base_url, &dir, &[]);
+    assert!(!ok);
+    assert!(output.contains("the site asked to wait 3"), "{output}");
+
+    assert_eq!(requests.lock().unwrap().len(), 1);
+    assert!(!dir.join("answers").exists());
+    fs::remove_dir_all(dir).unwrap();
+}
@@ -0,0 +1,37 @@
+//! `--trace` writes a Chrome trace with a span for every part.
+
+use std::{fs, process::Command};
+
+#[test]
+fn chrome_trace() {
+    let dir = std::env::temp_dir().join(format!("aoc-trace-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let input = dir.join("input");
+    let trace = dir.join("trace.json");
+    fs::write(&input, "1000\n2000\n\n4000\n\n5000\n").unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_aoc"))
+        .args(["--year", "2022", "--day", "1", "--time", "--input"])
+        .arg(&input)
+        .arg("--trace")
+        .arg(&trace)
+        .output()
+        .unwrap();
+    let trace = fs::read_to_string(&trace).unwrap();
+    fs::remove_dir_all(&dir).unwrap();
+
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stdout)
+    );
+    let events: Vec<serde_json::Value> = serde_json::from_str(&trace).unwrap();
+    for span in ["part1", "part2"] {
+        let begin = events
+            .iter()
+            .find(|event| event["name"] == span && event["ph"] == "B")
+            .unwrap_or_else(|| panic!("no {span} span in {trace}"));
+        assert_eq!(begin["args"]["year"], "2022");
+        assert_eq!(begin["args"]["day"], "1");
+    }
+}
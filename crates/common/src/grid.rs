@@ -0,0 +1,432 @@
+use std::{
+    fmt,
+    iter::successors,
+    ops::{Add, AddAssign, Index, IndexMut, Mul, Neg, Sub, SubAssign},
+    str::FromStr,
+};
+
+use crate::ParseError;
+
+/// A position on a [`Grid`]. `x` is the column and `y` the line of a character in the input, so
+/// `y` grows downwards. Coordinates may lie outside of any grid, which makes stepping off the
+/// edge safe.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Coord {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Coord {
+    pub const UP: Coord = Coord::new(0, -1);
+    pub const DOWN: Coord = Coord::new(0, 1);
+    pub const LEFT: Coord = Coord::new(-1, 0);
+    pub const RIGHT: Coord = Coord::new(1, 0);
+
+    /// Clockwise, starting upwards.
+    pub const DIRECTIONS_4: [Coord; 4] = [Coord::UP, Coord::RIGHT, Coord::DOWN, Coord::LEFT];
+    /// Clockwise including diagonals, starting upwards.
+    pub const DIRECTIONS_8: [Coord; 8] = [
+        Coord::new(0, -1),
+        Coord::new(1, -1),
+        Coord::new(1, 0),
+        Coord::new(1, 1),
+        Coord::new(0, 1),
+        Coord::new(-1, 1),
+        Coord::new(-1, 0),
+        Coord::new(-1, -1),
+    ];
+
+    pub const fn new(x: i64, y: i64) -> Self {
+        Coord { x, y }
+    }
+
+    pub fn neighbors4(self) -> impl Iterator<Item = Coord> {
+        Coord::DIRECTIONS_4.into_iter().map(move |dir| self + dir)
+    }
+
+    pub fn neighbors8(self) -> impl Iterator<Item = Coord> {
+        Coord::DIRECTIONS_8.into_iter().map(move |dir| self + dir)
+    }
+
+    /// Every coordinate reached by repeatedly stepping by `step`, not including `self`.
+    pub fn ray(self, step: Coord) -> impl Iterator<Item = Coord> {
+        successors(Some(self + step), move |&coord| Some(coord + step))
+    }
+
+    pub fn manhattan(self, other: Coord) -> u64 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    pub fn turn_right(self) -> Coord {
+        Coord::new(-self.y, self.x)
+    }
+
+    pub fn turn_left(self) -> Coord {
+        Coord::new(self.y, -self.x)
+    }
+}
+
+impl fmt::Debug for Coord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({},{})", self.x, self.y)
+    }
+}
+
+impl From<(i64, i64)> for Coord {
+    fn from((x, y): (i64, i64)) -> Self {
+        Coord { x, y }
+    }
+}
+
+impl Add for Coord {
+    type Output = Coord;
+    fn add(self, rhs: Coord) -> Coord {
+        Coord::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl AddAssign for Coord {
+    fn add_assign(&mut self, rhs: Coord) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Coord {
+    type Output = Coord;
+    fn sub(self, rhs: Coord) -> Coord {
+        Coord::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl SubAssign for Coord {
+    fn sub_assign(&mut self, rhs: Coord) {
+        *self = *self - rhs;
+    }
+}
+
+impl Mul<i64> for Coord {
+    type Output = Coord;
+    fn mul(self, rhs: i64) -> Coord {
+        Coord::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl Neg for Coord {
+    type Output = Coord;
+    fn neg(self) -> Coord {
+        Coord::new(-self.x, -self.y)
+    }
+}
+
+/// A rectangle of cells, stored row by row.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    /// Fails if the rows don't all have the same length.
+    pub fn from_rows(rows: impl IntoIterator<Item = Vec<T>>) -> Result<Self, GridError> {
+        let mut grid = Grid {
+            width: 0,
+            height: 0,
+            cells: Vec::new(),
+        };
+        for row in rows {
+            if grid.height == 0 {
+                grid.width = row.len();
+            } else if row.len() != grid.width {
+                return Err(GridError::Ragged {
+                    line: grid.height + 1,
+                    expected: grid.width,
+                    found: row.len(),
+                });
+            }
+            grid.cells.extend(row);
+            grid.height += 1;
+        }
+        Ok(grid)
+    }
+
+    /// One row per line, converting every character with `cell`.
+    pub fn parse(input: &str, mut cell: impl FnMut(char) -> Option<T>) -> Result<Self, GridError> {
+        let rows = input
+            .lines()
+            .enumerate()
+            .map(|(line, text)| {
+                text.chars()
+                    .enumerate()
+                    .map(|(column, c)| {
+                        cell(c).ok_or(GridError::InvalidCell {
+                            line: line + 1,
+                            column: column + 1,
+                            found: c,
+                        })
+                    })
+                    .collect()
+            })
+            .collect::<Result<Vec<Vec<T>>, _>>()?;
+        Grid::from_rows(rows)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, coord: Coord) -> bool {
+        (0..self.width as i64).contains(&coord.x) && (0..self.height as i64).contains(&coord.y)
+    }
+
+    fn index_of(&self, coord: Coord) -> Option<usize> {
+        self.contains(coord)
+            .then(|| coord.y as usize * self.width + coord.x as usize)
+    }
+
+    pub fn get(&self, coord: Coord) -> Option<&T> {
+        self.index_of(coord).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, coord: Coord) -> Option<&mut T> {
+        self.index_of(coord).map(|i| &mut self.cells[i])
+    }
+
+    /// Row by row, from the top left.
+    pub fn coords(&self) -> impl Iterator<Item = Coord> {
+        let width = self.width as i64;
+        (0..self.height as i64).flat_map(move |y| (0..width).map(move |x| Coord::new(x, y)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Coord, &T)> {
+        self.coords().zip(&self.cells)
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Coord, &mut T)> {
+        self.coords().zip(&mut self.cells)
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// The first coordinate, row by row, whose cell matches.
+    pub fn find(&self, mut matches: impl FnMut(&T) -> bool) -> Option<Coord> {
+        self.iter()
+            .find_map(|(coord, cell)| matches(cell).then_some(coord))
+    }
+
+    /// The orthogonal neighbors of `coord` inside the grid.
+    pub fn neighbors4(&self, coord: Coord) -> impl Iterator<Item = Coord> + '_ {
+        coord.neighbors4().filter(|&c| self.contains(c))
+    }
+
+    /// The orthogonal and diagonal neighbors of `coord` inside the grid.
+    pub fn neighbors8(&self, coord: Coord) -> impl Iterator<Item = Coord> + '_ {
+        coord.neighbors8().filter(|&c| self.contains(c))
+    }
+
+    /// The coordinates from `from` in steps of `step` until the edge of the grid, not including
+    /// `from`.
+    pub fn ray(&self, from: Coord, step: Coord) -> impl Iterator<Item = Coord> + '_ {
+        from.ray(step).take_while(|&c| self.contains(c))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T> Index<Coord> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, coord: Coord) -> &T {
+        self.get(coord)
+            .unwrap_or_else(|| panic!("{coord:?} is outside of the grid"))
+    }
+}
+
+impl<T> IndexMut<Coord> for Grid<T> {
+    fn index_mut(&mut self, coord: Coord) -> &mut T {
+        self.get_mut(coord)
+            .unwrap_or_else(|| panic!("{coord:?} is outside of the grid"))
+    }
+}
+
+impl FromStr for Grid<char> {
+    type Err = GridError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Grid::parse(s, Some)
+    }
+}
+
+impl Grid<u8> {
+    /// A grid of single digits, as heightmaps and risk levels come.
+    pub fn parse_digits(input: &str) -> Result<Self, GridError> {
+        Grid::parse(input, |c| c.to_digit(10).map(|d| d as u8))
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// Lines and columns start at 1.
+#[derive(Debug, PartialEq, Eq)]
+pub enum GridError {
+    Ragged {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+    InvalidCell {
+        line: usize,
+        column: usize,
+        found: char,
+    },
+}
+
+impl fmt::Display for GridError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GridError::Ragged {
+                line,
+                expected,
+                found,
+            } => write!(
+                f,
+                "line {line} has {found} cells, but the lines before have {expected}"
+            ),
+            GridError::InvalidCell {
+                line,
+                column,
+                found,
+            } => write!(f, "unexpected {found:?} at line {line}, column {column}"),
+        }
+    }
+}
+
+impl std::error::Error for GridError {}
+
+impl GridError {
+    /// The same error as a [`ParseError`] about the grid in the input of `day`.
+    pub fn locate(self, day: u32, input: &str) -> ParseError {
+        let line_of = |line: usize| input.lines().nth(line - 1).unwrap_or_default();
+        let error = match self {
+            GridError::Ragged { line, expected, .. } => ParseError::new(
+                line_of(line),
+                format_args!("expected {expected} cells like the lines before"),
+            ),
+            GridError::InvalidCell { line, column, .. } => {
+                let text = line_of(line);
+                let (start, found) = text
+                    .char_indices()
+                    .nth(column - 1)
+                    .expect("the cell is in its line");
+                ParseError::new(&text[start..start + found.len_utf8()], "invalid cell")
+            }
+        };
+        error.locate(day, input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let grid = Grid::parse_digits("123\n456\n").unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[Coord::new(2, 0)], 3);
+        assert_eq!(grid[Coord::new(0, 1)], 4);
+        assert_eq!(grid.get(Coord::new(3, 0)), None);
+        assert_eq!(grid.get(Coord::new(0, -1)), None);
+        assert_eq!(grid.to_string(), "123\n456\n");
+        assert_eq!(grid.find(|&d| d > 4), Some(Coord::new(1, 1)));
+
+        assert_eq!(
+            Grid::parse_digits("123\n4\n"),
+            Err(GridError::Ragged {
+                line: 2,
+                expected: 3,
+                found: 1
+            })
+        );
+        assert_eq!(
+            Grid::parse_digits("123\n4x6\n"),
+            Err(GridError::InvalidCell {
+                line: 2,
+                column: 2,
+                found: 'x'
+            })
+        );
+        let input = "123\n4x6\n";
+        let error = Grid::parse_digits(input).unwrap_err().locate(9, input);
+        assert_eq!((error.day, error.line, error.column), (9, 2, 2));
+        assert_eq!(error.text, "x");
+    }
+
+    #[test]
+    fn neighbors() {
+        let grid: Grid<char> = "abc\ndef\nghi".parse().unwrap();
+        let at = |coords: Vec<Coord>| coords.into_iter().map(|c| grid[c]).collect::<String>();
+
+        assert_eq!(at(grid.neighbors4(Coord::new(0, 0)).collect()), "bd");
+        assert_eq!(at(grid.neighbors8(Coord::new(0, 0)).collect()), "bed");
+        assert_eq!(at(grid.neighbors4(Coord::new(1, 1)).collect()), "bfhd");
+        assert_eq!(at(grid.neighbors8(Coord::new(1, 1)).collect()), "bcfihgda");
+        assert_eq!(Coord::new(5, 5).neighbors8().count(), 8);
+    }
+
+    #[test]
+    fn rays() {
+        let grid: Grid<char> = "abc\ndef\nghi".parse().unwrap();
+        let ray = |from, step| grid.ray(from, step).map(|c| grid[c]).collect::<String>();
+
+        assert_eq!(ray(Coord::new(0, 0), Coord::RIGHT), "bc");
+        assert_eq!(ray(Coord::new(2, 2), Coord::new(-1, -1)), "ea");
+        assert_eq!(ray(Coord::new(1, 0), Coord::UP), "");
+        assert_eq!(
+            Coord::new(0, 0).ray(Coord::DOWN * 2).nth(2),
+            Some(Coord::new(0, 6))
+        );
+    }
+
+    #[test]
+    fn coords() {
+        assert_eq!(Coord::new(1, 2).manhattan(Coord::new(-2, 0)), 5);
+        assert_eq!(Coord::UP.turn_right(), Coord::RIGHT);
+        assert_eq!(Coord::RIGHT.turn_right(), Coord::DOWN);
+        assert_eq!(Coord::UP.turn_left(), Coord::LEFT);
+        assert_eq!(-Coord::UP, Coord::DOWN);
+    }
+}
@@ -0,0 +1,130 @@
+use std::{
+    env, fmt, fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+
+/// Overrides the directory inputs are read from.
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+/// Where puzzle inputs come from.
+#[derive(Debug, Clone)]
+pub enum InputSource {
+    /// `{dir}/{year}/{day}`, the layout `aoc fetch` writes.
+    Dir(PathBuf),
+    /// The same file for whatever day is asked for.
+    File(PathBuf),
+    Stdin,
+}
+
+impl InputSource {
+    pub fn from_env() -> Self {
+        InputSource::Dir(default_dir())
+    }
+
+    pub fn read(&self, year: u32, day: u32) -> Result<String, InputError> {
+        match self {
+            InputSource::Dir(dir) => read_file(&input_path(dir, year, day)),
+            InputSource::File(path) => read_file(path),
+            InputSource::Stdin => {
+                let mut input = String::new();
+                io::stdin()
+                    .read_to_string(&mut input)
+                    .map_err(InputError::Stdin)?;
+                Ok(input)
+            }
+        }
+    }
+}
+
+/// The input directory from `AOC_INPUT_DIR`, or `inputs/` in the workspace root.
+pub fn default_dir() -> PathBuf {
+    env::var_os(INPUT_DIR_VAR)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| {
+            let workspace = Path::new(env!("CARGO_MANIFEST_DIR")).ancestors().nth(2);
+            workspace
+                .expect("crate is inside the workspace")
+                .join("inputs")
+        })
+}
+
+pub fn input_path(dir: &Path, year: u32, day: u32) -> PathBuf {
+    dir.join(year.to_string()).join(day.to_string())
+}
+
+/// Reads the input of a day from the default input directory.
+pub fn read_input(year: u32, day: u32) -> Result<String, InputError> {
+    InputSource::from_env().read(year, day)
+}
+
+fn read_file(path: &Path) -> Result<String, InputError> {
+    fs::read_to_string(path).map_err(|source| {
+        if source.kind() == io::ErrorKind::NotFound {
+            InputError::Missing {
+                path: path.to_owned(),
+            }
+        } else {
+            InputError::Io {
+                path: path.to_owned(),
+                source,
+            }
+        }
+    })
+}
+
+#[derive(Debug)]
+pub enum InputError {
+    Missing { path: PathBuf },
+    Io { path: PathBuf, source: io::Error },
+    Stdin(io::Error),
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputError::Missing { path } => write!(
+                f,
+                "no input file at {} (set {INPUT_DIR_VAR} or run `aoc fetch`)",
+                path.display()
+            ),
+            InputError::Io { path, source } => {
+                write!(f, "failed to read {}: {source}", path.display())
+            }
+            InputError::Stdin(source) => write!(f, "failed to read stdin: {source}"),
+        }
+    }
+}
+
+impl std::error::Error for InputError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            InputError::Missing { .. } => None,
+            InputError::Io { source, .. } | InputError::Stdin(source) => Some(source),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_year_day_layout() {
+        let dir = env::temp_dir().join(format!("aoc-inputs-{}", std::process::id()));
+        fs::create_dir_all(dir.join("2021")).unwrap();
+        fs::write(dir.join("2021/6"), "3,4,3,1,2\n").unwrap();
+
+        let source = InputSource::Dir(dir.clone());
+        assert_eq!(source.read(2021, 6).unwrap(), "3,4,3,1,2\n");
+        assert!(matches!(
+            source.read(2021, 7),
+            Err(InputError::Missing { path }) if path == dir.join("2021/7")
+        ));
+
+        let file = InputSource::File(dir.join("2021/6"));
+        assert_eq!(file.read(2024, 1).unwrap(), "3,4,3,1,2\n");
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,15 @@
+mod answer;
+pub mod grid;
+pub mod input;
+pub mod ocr;
+pub mod parse;
+pub mod path;
+pub mod shape;
+mod solution;
+
+pub use answer::Answer;
+pub use grid::{Coord, Grid};
+pub use input::{read_input, InputError, InputSource};
+pub use parse::ParseError;
+pub use shape::Shape;
+pub use solution::{parsed, Puzzle, Solution};
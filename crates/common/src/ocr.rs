@@ -0,0 +1,282 @@
+//! Reads the capital letters some puzzles draw with `#` instead of printing their answer.
+//!
+//! Two fonts show up: letters 6 pixels high, usually 4 wide, and letters 10 pixels high and 6
+//! wide. Letters are told apart by the empty columns between them, so the exact spacing doesn't
+//! matter.
+
+use std::fmt;
+
+const SMALL: &[(char, &str)] = &[
+    ('A', ".##.\n#..#\n#..#\n####\n#..#\n#..#"),
+    ('B', "###.\n#..#\n###.\n#..#\n#..#\n###."),
+    ('C', ".##.\n#..#\n#...\n#...\n#..#\n.##."),
+    ('E', "####\n#...\n###.\n#...\n#...\n####"),
+    ('F', "####\n#...\n###.\n#...\n#...\n#..."),
+    ('G', ".##.\n#..#\n#...\n#.##\n#..#\n.###"),
+    ('H', "#..#\n#..#\n####\n#..#\n#..#\n#..#"),
+    ('I', ".###\n..#.\n..#.\n..#.\n..#.\n.###"),
+    ('J', "..##\n...#\n...#\n...#\n#..#\n.##."),
+    ('K', "#..#\n#.#.\n##..\n#.#.\n#.#.\n#..#"),
+    ('L', "#...\n#...\n#...\n#...\n#...\n####"),
+    ('O', ".##.\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('P', "###.\n#..#\n#..#\n###.\n#...\n#..."),
+    ('R', "###.\n#..#\n#..#\n###.\n#.#.\n#..#"),
+    ('S', ".###\n#...\n#...\n.##.\n...#\n###."),
+    ('U', "#..#\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('Y', "#...#\n#...#\n.#.#.\n..#..\n..#..\n..#.."),
+    ('Z', "####\n...#\n..#.\n.#..\n#...\n####"),
+];
+
+const LARGE: &[(char, &str)] = &[
+    (
+        'A',
+        "..##..\n.#..#.\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#",
+    ),
+    (
+        'B',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#....#\n#....#\n#....#\n#....#\n#####.",
+    ),
+    (
+        'C',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#....#\n.####.",
+    ),
+    (
+        'E',
+        "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n######",
+    ),
+    (
+        'F',
+        "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+    ),
+    (
+        'G',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n#..###\n#....#\n#....#\n#...##\n.###.#",
+    ),
+    (
+        'H',
+        "#....#\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#\n#....#",
+    ),
+    (
+        'J',
+        "...###\n....#.\n....#.\n....#.\n....#.\n....#.\n....#.\n#...#.\n#...#.\n.###..",
+    ),
+    (
+        'K',
+        "#....#\n#...#.\n#..#..\n#.#...\n##....\n##....\n#.#...\n#..#..\n#...#.\n#....#",
+    ),
+    (
+        'L',
+        "#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n######",
+    ),
+    (
+        'N',
+        "#....#\n##...#\n##...#\n#.#..#\n#.#..#\n#..#.#\n#..#.#\n#...##\n#...##\n#....#",
+    ),
+    (
+        'P',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+    ),
+    (
+        'R',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#..#..\n#...#.\n#...#.\n#....#\n#....#",
+    ),
+    (
+        'X',
+        "#....#\n#....#\n.#..#.\n.#..#.\n..##..\n..##..\n.#..#.\n.#..#.\n#....#\n#....#",
+    ),
+    (
+        'Z',
+        "######\n.....#\n.....#\n....#.\n...#..\n..#...\n.#....\n#.....\n#.....\n######",
+    ),
+];
+
+/// Reads the letters in a picture of `#`s. Any other character is an unlit pixel, and empty
+/// lines around the letters are ignored.
+pub fn read_letters(picture: &str) -> Result<String, OcrError> {
+    let lines: Vec<&str> = picture.lines().map(str::trim_end).collect();
+    let lit_lines = |line: &&str| line.contains('#');
+    let first = lines.iter().position(lit_lines).ok_or(OcrError::Empty)?;
+    let last = lines
+        .iter()
+        .rposition(lit_lines)
+        .expect("there is a lit line");
+    let pixels = Pixels(
+        lines[first..=last]
+            .iter()
+            .map(|line| line.chars().map(|c| c == '#').collect())
+            .collect(),
+    );
+    let font = match pixels.height() {
+        6 => SMALL,
+        10 => LARGE,
+        height => return Err(OcrError::Height(height)),
+    };
+
+    pixels
+        .glyphs()
+        .map(|(column, glyph)| {
+            font.iter()
+                .find(|(_, known)| Pixels::parse(known).trimmed() == glyph)
+                .map(|&(letter, _)| letter)
+                .ok_or_else(|| OcrError::Unknown {
+                    column: column + 1,
+                    glyph: glyph.to_string(),
+                })
+        })
+        .collect()
+}
+
+/// Rows of pixels, `true` if lit.
+#[derive(PartialEq, Eq)]
+struct Pixels(Vec<Vec<bool>>);
+
+impl Pixels {
+    fn parse(glyph: &str) -> Self {
+        Pixels(
+            glyph
+                .lines()
+                .map(|line| line.chars().map(|c| c == '#').collect())
+                .collect(),
+        )
+    }
+
+    fn height(&self) -> usize {
+        self.0.len()
+    }
+
+    fn width(&self) -> usize {
+        self.0.iter().map(Vec::len).max().unwrap_or(0)
+    }
+
+    fn lit(&self, column: usize) -> bool {
+        self.0.iter().any(|row| row.get(column) == Some(&true))
+    }
+
+    fn columns(&self, columns: std::ops::Range<usize>) -> Self {
+        Pixels(
+            self.0
+                .iter()
+                .map(|row| columns.clone().map(|x| row.get(x) == Some(&true)).collect())
+                .collect(),
+        )
+    }
+
+    /// Without the empty columns on either side.
+    fn trimmed(&self) -> Self {
+        let first = (0..self.width()).find(|&x| self.lit(x)).unwrap_or(0);
+        let end = (0..self.width())
+            .rfind(|&x| self.lit(x))
+            .map_or(0, |x| x + 1);
+        self.columns(first..end)
+    }
+
+    /// Every run of columns with lit pixels, with the index of its first column.
+    fn glyphs(&self) -> impl Iterator<Item = (usize, Pixels)> + '_ {
+        let width = self.width();
+        let mut x = 0;
+        std::iter::from_fn(move || {
+            let start = (x..width).find(|&x| self.lit(x))?;
+            let end = (start..width).find(|&x| !self.lit(x)).unwrap_or(width);
+            x = end;
+            Some((start, self.columns(start..end)))
+        })
+    }
+}
+
+impl fmt::Display for Pixels {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in &self.0 {
+            for &lit in row {
+                write!(f, "{}", if lit { '#' } else { '.' })?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// Columns start at 1.
+#[derive(Debug, PartialEq, Eq)]
+pub enum OcrError {
+    Empty,
+    /// Neither font has letters of this height.
+    Height(usize),
+    Unknown {
+        column: usize,
+        glyph: String,
+    },
+}
+
+impl fmt::Display for OcrError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OcrError::Empty => write!(f, "there are no letters in the picture"),
+            OcrError::Height(height) => {
+                write!(f, "letters are 6 or 10 pixels high, not {height}")
+            }
+            OcrError::Unknown { column, glyph } => {
+                write!(f, "unknown letter at column {column}:\n{glyph}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for OcrError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Draws `letters` side by side, `gap` columns apart.
+    fn draw(font: &[(char, &str)], letters: &str, gap: usize) -> String {
+        let glyphs: Vec<Vec<&str>> = letters
+            .chars()
+            .map(|letter| {
+                let (_, glyph) = font.iter().find(|(c, _)| *c == letter).unwrap();
+                glyph.lines().collect()
+            })
+            .collect();
+        (0..glyphs[0].len())
+            .map(|y| {
+                let row: Vec<&str> = glyphs.iter().map(|glyph| glyph[y]).collect();
+                row.join(&".".repeat(gap)) + "\n"
+            })
+            .collect()
+    }
+
+    #[test]
+    fn fonts() {
+        let small: String = SMALL.iter().map(|(c, _)| c).collect();
+        assert_eq!(read_letters(&draw(SMALL, &small, 1)), Ok(small));
+        let large: String = LARGE.iter().map(|(c, _)| c).collect();
+        assert_eq!(read_letters(&draw(LARGE, &large, 2)), Ok(large));
+    }
+
+    #[test]
+    fn crt() {
+        let picture = "
+####...##..##..####.###...##..#....#..#.
+#.......#.#..#.#....#..#.#..#.#....#..#.
+###.....#.#....###..#..#.#....#....####.
+#.......#.#....#....###..#.##.#....#..#.
+#....#..#.#..#.#....#....#..#.#....#..#.
+####..##...##..#....#.....###.####.#..#.
+";
+        assert_eq!(read_letters(picture).unwrap(), "EJCFPGLH");
+        assert_eq!(
+            read_letters(&picture.replace('.', " ")).unwrap(),
+            "EJCFPGLH"
+        );
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(read_letters("...\n"), Err(OcrError::Empty));
+        assert_eq!(read_letters("#\n#\n#"), Err(OcrError::Height(3)));
+        let block = ".####\n".repeat(6);
+        assert!(matches!(
+            read_letters(&block),
+            Err(OcrError::Unknown { column: 2, .. })
+        ));
+    }
+}
@@ -0,0 +1,166 @@
+//! Errors for inputs that aren't shaped like the puzzle says, pointing at the offending text.
+//!
+//! Parsers usually only see a line or a block of the input, so they create errors with
+//! [`ParseError::new`] from the slice they choke on. Whoever has the whole input then calls
+//! [`ParseError::locate`] to fill in the day, line and column.
+
+use std::{fmt, str::FromStr};
+
+/// Lines and columns start at 1, columns count characters. Day, line and column are 0 until
+/// the error is located, and left out when it's displayed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub day: u32,
+    pub line: usize,
+    pub column: usize,
+    /// What couldn't be parsed. Empty if something is missing, like at the end of a cut off input.
+    pub text: String,
+    pub reason: String,
+    /// Where `text` starts in memory, to find it in the input later. Never dereferenced.
+    address: usize,
+}
+
+impl ParseError {
+    /// An error about `text`, which should be a slice of the input so that
+    /// [`locate`](ParseError::locate) can find it.
+    pub fn new(text: &str, reason: impl fmt::Display) -> Self {
+        ParseError {
+            day: 0,
+            line: 0,
+            column: 0,
+            text: text.to_string(),
+            reason: reason.to_string(),
+            address: text.as_ptr() as usize,
+        }
+    }
+
+    /// Something is missing after `text`, usually because the input is cut off.
+    pub fn missing(text: &str, what: impl fmt::Display) -> Self {
+        ParseError::new(&text[text.len()..], format_args!("expected {what}"))
+    }
+
+    /// Fills in the day and where the offending text is in `input`. Errors about text from
+    /// somewhere else keep line and column 0.
+    pub fn locate(self, day: u32, input: &str) -> Self {
+        let start = input.as_ptr() as usize;
+        let Some(offset) = self
+            .address
+            .checked_sub(start)
+            .filter(|&offset| offset <= input.len() && input.is_char_boundary(offset))
+        else {
+            return ParseError { day, ..self };
+        };
+        let before = &input[..offset];
+        let line_start = before.rfind('\n').map_or(0, |newline| newline + 1);
+        ParseError {
+            day,
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            ..self
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.day == 0 {
+            write!(f, "{}", self.reason)?;
+        } else if self.line == 0 {
+            write!(f, "day {}: {}", self.day, self.reason)?;
+        } else {
+            write!(
+                f,
+                "day {}, line {}, column {}: {}",
+                self.day, self.line, self.column, self.reason
+            )?;
+        }
+        if self.text.is_empty() {
+            write!(f, ", found nothing")
+        } else {
+            write!(f, ", found {:?}", self.text)
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parses every line of `input` with `T`'s [`FromStr`].
+pub fn lines<T: FromStr<Err = ParseError>>(day: u32, input: &str) -> Result<Vec<T>, ParseError> {
+    input
+        .lines()
+        .map(|line| line.parse().map_err(|e: ParseError| e.locate(day, input)))
+        .collect()
+}
+
+/// `text` split around the first `separator`.
+pub fn split_once<'a>(text: &'a str, separator: &str) -> Result<(&'a str, &'a str), ParseError> {
+    text.split_once(separator)
+        .ok_or_else(|| ParseError::new(text, format_args!("expected {separator:?}")))
+}
+
+/// `text` without `prefix`.
+pub fn strip_prefix<'a>(text: &'a str, prefix: &str) -> Result<&'a str, ParseError> {
+    text.strip_prefix(prefix)
+        .ok_or_else(|| ParseError::new(text, format_args!("expected {prefix:?}")))
+}
+
+/// A number, or anything else whose parse error is only worth its message.
+pub fn number<T>(text: &str) -> Result<T, ParseError>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    text.parse().map_err(|e| ParseError::new(text, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn locations() {
+        let input = "1 2\nä 3 x\n";
+        let x = &input[input.find('x').unwrap()..][..1];
+        let error = number::<u32>(x).unwrap_err().locate(5, input);
+        assert_eq!((error.day, error.line, error.column), (5, 2, 5));
+        assert_eq!(
+            error.to_string(),
+            r#"day 5, line 2, column 5: invalid digit found in string, found "x""#
+        );
+
+        let error = ParseError::missing(input.trim_end(), "a number").locate(5, input);
+        assert_eq!((error.line, error.column), (2, 6));
+        assert_eq!(
+            error.to_string(),
+            "day 5, line 2, column 6: expected a number, found nothing"
+        );
+
+        let elsewhere = ParseError::new(&String::from("x"), "no").locate(5, input);
+        assert_eq!((elsewhere.line, elsewhere.column), (0, 0));
+        assert_eq!(elsewhere.to_string(), r#"day 5: no, found "x""#);
+        // Not located at all, so there's no day to show either.
+        let unlocated = ParseError::new("x", "no");
+        assert_eq!(unlocated.to_string(), r#"no, found "x""#);
+    }
+
+    #[test]
+    fn lines_are_located() {
+        #[derive(Debug, PartialEq)]
+        struct Pair(u32, u32);
+        impl FromStr for Pair {
+            type Err = ParseError;
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                let (l, r) = split_once(s, ",")?;
+                Ok(Pair(number(l)?, number(r)?))
+            }
+        }
+
+        assert_eq!(
+            lines::<Pair>(1, "1,2\n3,4\n").unwrap(),
+            [Pair(1, 2), Pair(3, 4)]
+        );
+        let error = lines::<Pair>(1, "1,2\n3;4\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+        assert_eq!(error.reason, r#"expected ",""#);
+    }
+}
@@ -0,0 +1,254 @@
+//! Shortest paths over any graph, described by a function listing a node's neighbors.
+//!
+//! Every search takes several start nodes, so "closest of many starts" needs no extra work, and
+//! stops at the first node `is_goal` accepts.
+
+use std::{
+    cmp::Reverse,
+    collections::{hash_map::Entry, BinaryHeap, HashMap, VecDeque},
+    hash::Hash,
+};
+
+/// A cheapest path, from its start to the goal, both included.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<N> {
+    pub nodes: Vec<N>,
+    pub cost: u64,
+}
+
+impl<N> Path<N> {
+    /// The number of steps taken, one less than the number of nodes.
+    pub fn len(&self) -> usize {
+        self.nodes.len() - 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn start(&self) -> &N {
+        &self.nodes[0]
+    }
+
+    pub fn goal(&self) -> &N {
+        self.nodes.last().expect("paths aren't empty")
+    }
+}
+
+/// Finds the path with the fewest steps, every step costs 1.
+pub fn bfs<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbors: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut seen = Seen::default();
+    let mut queue = VecDeque::new();
+    for start in starts {
+        if let Some(index) = seen.improve(start, None, 0) {
+            queue.push_back(index);
+        }
+    }
+    while let Some(index) = queue.pop_front() {
+        let node = seen.nodes[index].node.clone();
+        if is_goal(&node) {
+            return Some(seen.path(index));
+        }
+        let cost = seen.nodes[index].cost + 1;
+        for next in neighbors(&node) {
+            if let Some(next) = seen.improve(next, Some(index), cost) {
+                queue.push_back(next);
+            }
+        }
+    }
+    None
+}
+
+/// Finds the cheapest path, where moving from one node to a neighbor costs `cost(from, to)`.
+pub fn dijkstra<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    neighbors: impl FnMut(&N) -> I,
+    cost: impl FnMut(&N, &N) -> u64,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    astar(starts, neighbors, cost, |_| 0, is_goal)
+}
+
+/// Like [`dijkstra`], but looks at nodes the `heuristic` deems close to a goal first. The
+/// heuristic must never overestimate the remaining cost, or the path found may not be the
+/// cheapest.
+pub fn astar<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbors: impl FnMut(&N) -> I,
+    mut cost: impl FnMut(&N, &N) -> u64,
+    mut heuristic: impl FnMut(&N) -> u64,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut seen = Seen::default();
+    let mut queue = BinaryHeap::new();
+    for start in starts {
+        if let Some(index) = seen.improve(start, None, 0) {
+            let estimate = heuristic(&seen.nodes[index].node);
+            queue.push(Reverse((estimate, 0, index)));
+        }
+    }
+    while let Some(Reverse((_, cost_so_far, index))) = queue.pop() {
+        if cost_so_far > seen.nodes[index].cost {
+            // Reached more cheaply since this was queued.
+            continue;
+        }
+        let node = seen.nodes[index].node.clone();
+        if is_goal(&node) {
+            return Some(seen.path(index));
+        }
+        for next in neighbors(&node) {
+            let next_cost = cost_so_far + cost(&node, &next);
+            if let Some(next) = seen.improve(next, Some(index), next_cost) {
+                let estimate = next_cost + heuristic(&seen.nodes[next].node);
+                queue.push(Reverse((estimate, next_cost, next)));
+            }
+        }
+    }
+    None
+}
+
+struct Visit<N> {
+    node: N,
+    previous: Option<usize>,
+    cost: u64,
+}
+
+/// Every node reached so far with the cheapest known way to get there. Nodes are referred to by
+/// their index so that they don't need to be ordered to be queued.
+struct Seen<N> {
+    nodes: Vec<Visit<N>>,
+    indices: HashMap<N, usize>,
+}
+
+impl<N> Default for Seen<N> {
+    fn default() -> Self {
+        Seen {
+            nodes: Vec::new(),
+            indices: HashMap::new(),
+        }
+    }
+}
+
+impl<N: Clone + Eq + Hash> Seen<N> {
+    /// Records reaching `node` from `previous` for `cost`, returning its index if that's the
+    /// first or a cheaper way to get there.
+    fn improve(&mut self, node: N, previous: Option<usize>, cost: u64) -> Option<usize> {
+        match self.indices.entry(node) {
+            Entry::Occupied(entry) => {
+                let index = *entry.get();
+                let visit = &mut self.nodes[index];
+                if cost >= visit.cost {
+                    return None;
+                }
+                visit.previous = previous;
+                visit.cost = cost;
+                Some(index)
+            }
+            Entry::Vacant(entry) => {
+                let index = self.nodes.len();
+                self.nodes.push(Visit {
+                    node: entry.key().clone(),
+                    previous,
+                    cost,
+                });
+                entry.insert(index);
+                Some(index)
+            }
+        }
+    }
+
+    fn path(&self, goal: usize) -> Path<N> {
+        let mut nodes = Vec::new();
+        let mut index = Some(goal);
+        while let Some(current) = index {
+            nodes.push(self.nodes[current].node.clone());
+            index = self.nodes[current].previous;
+        }
+        nodes.reverse();
+        Path {
+            nodes,
+            cost: self.nodes[goal].cost,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Coord, Grid};
+
+    use super::*;
+
+    const MAZE: &str = "\
+S..#....
+.#.#.##.
+.#...#..
+.####.#.
+......#G";
+
+    #[test]
+    fn shortest_paths() {
+        let maze: Grid<char> = MAZE.parse().unwrap();
+        let start = maze.find(|&c| c == 'S').unwrap();
+        let goal = maze.find(|&c| c == 'G').unwrap();
+        let open = |coord: &Coord| maze.neighbors4(*coord).filter(|&c| maze[c] != '#');
+
+        let path = bfs([start], open, |&c| c == goal).unwrap();
+        assert_eq!(path.len(), 15);
+        assert_eq!((*path.start(), *path.goal()), (start, goal));
+        assert!(path.nodes.windows(2).all(|w| w[0].manhattan(w[1]) == 1));
+
+        let weighted = dijkstra([start], open, |_, _| 1, |&c| c == goal).unwrap();
+        assert_eq!(weighted.cost, 15);
+        let guided = astar(
+            [start],
+            open,
+            |_, _| 1,
+            |c| c.manhattan(goal),
+            |&c| c == goal,
+        );
+        assert_eq!(guided.unwrap().cost, 15);
+
+        let walled_in = |_: &Coord| std::iter::empty();
+        assert_eq!(bfs([start], walled_in, |&c| c == goal), None);
+    }
+
+    #[test]
+    fn weights() {
+        // Going straight through the 9s is shorter but more expensive than going around.
+        let risk = Grid::parse_digits("1911\n1919\n1119").unwrap();
+        let goal = Coord::new(3, 0);
+        let path = dijkstra(
+            [Coord::new(0, 0)],
+            |&c| risk.neighbors4(c).collect::<Vec<_>>(),
+            |_, &to| risk[to] as u64,
+            |&c| c == goal,
+        )
+        .unwrap();
+        assert_eq!(path.cost, 7);
+        assert_eq!(path.len(), 7);
+    }
+
+    #[test]
+    fn multiple_starts() {
+        let line = |&n: &i64| [n - 1, n + 1];
+        let path = bfs([0, 10, 20], line, |&n| n == 13).unwrap();
+        assert_eq!(path.nodes, [10, 11, 12, 13]);
+        assert_eq!(bfs([13], line, |&n| n == 13).unwrap().len(), 0);
+    }
+}
@@ -0,0 +1,262 @@
+//! What a day's input is expected to look like, so a wrong or mangled input file is caught
+//! before a solution trips over it. Patterns are regexes matched against whole lines.
+
+use regex::Regex;
+
+use crate::ParseError;
+
+/// The structure of an input, declared by [`Solution::input_shape`](crate::Solution).
+#[derive(Debug)]
+pub enum Shape {
+    /// A single line matching the pattern.
+    Line(&'static str),
+    /// Lines that each match the pattern.
+    Lines(&'static str),
+    /// Lines of equal length whose characters each match the `cell` pattern, optionally with
+    /// a fixed `(width, height)`.
+    Grid {
+        cell: &'static str,
+        size: Option<(usize, usize)>,
+    },
+    /// Blocks separated by blank lines, each with the shape at its position. The last shape
+    /// applies to all remaining blocks.
+    Blocks(&'static [Shape]),
+    /// Lines that match the patterns in turn, starting over after the last one.
+    Cycle(&'static [&'static str]),
+}
+
+/// Every mismatch between `input` and the expectations every input meets, plus `shape` if
+/// there is one.
+pub fn check(day: u32, input: &str, shape: Option<&Shape>) -> Vec<ParseError> {
+    let mut errors = Vec::new();
+    if input.is_empty() {
+        errors.push(ParseError::missing(input, "an input"));
+    }
+    if let Some(i) = input.find('\r') {
+        errors.push(ParseError::new(
+            &input[i..i + 1],
+            "expected Unix line endings",
+        ));
+    }
+    let content = input.trim_end_matches('\n');
+    if !input.is_empty() && !input.ends_with('\n') {
+        errors.push(ParseError::missing(input, "a newline at the end"));
+    } else if input.len() > content.len() + 1 {
+        errors.push(ParseError::new(
+            &input[content.len() + 1..],
+            "expected no blank lines at the end",
+        ));
+    }
+    if let Some(shape) = shape.filter(|_| !content.is_empty()) {
+        shape.check(content, &mut errors);
+    }
+    let mut errors: Vec<ParseError> = errors
+        .into_iter()
+        .map(|error| error.locate(day, input))
+        .collect();
+    errors.sort_by_key(|error| (error.line, error.column));
+    errors
+}
+
+impl Shape {
+    /// Checks `text`, a slice of the input without the final newline.
+    fn check(&self, text: &str, errors: &mut Vec<ParseError>) {
+        match self {
+            Shape::Line(pattern) => {
+                let mut lines = text.lines();
+                line(
+                    &regex(pattern),
+                    pattern,
+                    lines.next().unwrap_or(text),
+                    errors,
+                );
+                if let Some(extra) = lines.next() {
+                    errors.push(ParseError::new(extra, "expected a single line"));
+                }
+            }
+            Shape::Lines(pattern) => {
+                let regex = regex(pattern);
+                for text in text.lines() {
+                    line(&regex, pattern, text, errors);
+                }
+            }
+            Shape::Grid { cell, size } => grid(cell, *size, text, errors),
+            Shape::Blocks(shapes) => {
+                let last = shapes.last().expect("blocks have a shape");
+                for (i, block) in text.split("\n\n").enumerate() {
+                    shapes.get(i).unwrap_or(last).check(block, errors);
+                }
+                if text.split("\n\n").count() < shapes.len() {
+                    errors.push(ParseError::missing(
+                        text,
+                        format!("at least {} blocks separated by blank lines", shapes.len()),
+                    ));
+                }
+            }
+            Shape::Cycle(patterns) => {
+                let regexes: Vec<Regex> = patterns.iter().map(|pattern| regex(pattern)).collect();
+                let mut count = 0;
+                for (i, text) in text.lines().enumerate() {
+                    let i = i % patterns.len();
+                    line(&regexes[i], patterns[i], text, errors);
+                    count += 1;
+                }
+                if count % patterns.len() != 0 {
+                    errors.push(ParseError::missing(
+                        text,
+                        format!("a line matching /{}/", patterns[count % patterns.len()]),
+                    ));
+                }
+            }
+        }
+    }
+}
+
+fn regex(pattern: &str) -> Regex {
+    Regex::new(&format!("^(?:{pattern})$")).expect("shape patterns are valid regexes")
+}
+
+fn line(regex: &Regex, pattern: &str, text: &str, errors: &mut Vec<ParseError>) {
+    // Windows line endings are reported once for the whole input.
+    let text = text.strip_suffix('\r').unwrap_or(text);
+    if regex.is_match(text) {
+        return;
+    }
+    let trimmed = text.trim_end();
+    if trimmed.len() < text.len() && regex.is_match(trimmed) {
+        errors.push(ParseError::new(
+            &text[trimmed.len()..],
+            "expected no whitespace at the end of the line",
+        ));
+    } else {
+        errors.push(ParseError::new(
+            text,
+            format!("expected a line matching /{pattern}/"),
+        ));
+    }
+}
+
+fn grid(cell: &str, size: Option<(usize, usize)>, text: &str, errors: &mut Vec<ParseError>) {
+    let regex = regex(cell);
+    let lines: Vec<&str> = text.lines().collect();
+    let width = size.map_or_else(|| lines[0].chars().count(), |(width, _)| width);
+    for &line in &lines {
+        let mut cells = 0;
+        for (i, c) in line.char_indices() {
+            cells += 1;
+            if !regex.is_match(&line[i..i + c.len_utf8()]) {
+                errors.push(ParseError::new(
+                    &line[i..i + c.len_utf8()],
+                    format!("expected a cell matching /{cell}/"),
+                ));
+                return;
+            }
+        }
+        if cells != width {
+            errors.push(ParseError::new(line, format!("expected {width} cells")));
+            return;
+        }
+    }
+    if let Some((_, height)) = size.filter(|&(_, height)| height != lines.len()) {
+        errors.push(ParseError::new(
+            lines.last().copied().unwrap_or(text),
+            format!("expected {height} lines, not {}", lines.len()),
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn errors(input: &str, shape: Shape) -> Vec<String> {
+        check(1, input, Some(&shape))
+            .iter()
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn whitespace() {
+        let lines = || Shape::Lines(r"\d+");
+        assert!(errors("1\n2\n", lines()).is_empty());
+        assert_eq!(
+            errors("1\r\n2", lines()),
+            [
+                "day 1, line 1, column 2: expected Unix line endings, found \"\\r\"",
+                "day 1, line 2, column 2: expected a newline at the end, found nothing",
+            ]
+        );
+        assert_eq!(
+            errors("1 \n2\n\n", lines()),
+            [
+                "day 1, line 1, column 2: expected no whitespace at the end of the line, found \" \"",
+                "day 1, line 3, column 1: expected no blank lines at the end, found \"\\n\"",
+            ]
+        );
+        assert_eq!(
+            errors("", lines()),
+            ["day 1, line 1, column 1: expected an input, found nothing"]
+        );
+    }
+
+    #[test]
+    fn lines() {
+        assert_eq!(
+            errors("R 4\nQ 1\n", Shape::Lines(r"[LRUD] \d+")),
+            ["day 1, line 2, column 1: expected a line matching /[LRUD] \\d+/, found \"Q 1\""]
+        );
+        assert_eq!(
+            errors("ABC\nDEF\n", Shape::Line("[A-F]+")),
+            ["day 1, line 2, column 1: expected a single line, found \"DEF\""]
+        );
+    }
+
+    #[test]
+    fn grids() {
+        let grid = || Shape::Grid {
+            cell: "[0-9]",
+            size: Some((3, 2)),
+        };
+        assert!(errors("123\n456\n", grid()).is_empty());
+        assert_eq!(
+            errors("123\n4x6\n", grid()),
+            ["day 1, line 2, column 2: expected a cell matching /[0-9]/, found \"x\""]
+        );
+        assert_eq!(
+            errors("123\n45\n", grid()),
+            ["day 1, line 2, column 1: expected 3 cells, found \"45\""]
+        );
+        assert_eq!(
+            errors("123\n456\n789\n", grid()),
+            ["day 1, line 3, column 1: expected 2 lines, not 3, found \"789\""]
+        );
+    }
+
+    #[test]
+    fn blocks() {
+        let blocks = || Shape::Blocks(&[Shape::Line(r"\d+(,\d+)*"), Shape::Lines("[a-z]+")]);
+        assert!(errors("1,2\n\nab\ncd\n\nef\n", blocks()).is_empty());
+        assert_eq!(
+            errors("1,2\n\nab\n\nc2\n", blocks()),
+            ["day 1, line 5, column 1: expected a line matching /[a-z]+/, found \"c2\""]
+        );
+        assert_eq!(
+            errors("1,2\n", blocks()),
+            ["day 1, line 1, column 4: expected at least 2 blocks separated by blank lines, found nothing"]
+        );
+    }
+
+    #[test]
+    fn cycles() {
+        let cycle = || Shape::Cycle(&["inp w", r"add x -?\d+"]);
+        assert!(errors("inp w\nadd x 1\ninp w\nadd x -2\n", cycle()).is_empty());
+        assert_eq!(
+            errors("inp w\nadd x 1\nadd x 2\n", cycle()),
+            [
+                "day 1, line 3, column 1: expected a line matching /inp w/, found \"add x 2\"",
+                "day 1, line 3, column 8: expected a line matching /add x -?\\d+/, found nothing",
+            ]
+        );
+    }
+}
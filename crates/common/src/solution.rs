@@ -0,0 +1,81 @@
+use eyre::Result;
+use tracing::info_span;
+
+use crate::{Answer, Shape};
+
+/// A solved day. Both parts get the raw puzzle input and parse it themselves.
+pub trait Solution: Sync {
+    fn part1(&self, input: &str) -> Result<Answer>;
+
+    fn part2(&self, input: &str) -> Result<Answer>;
+
+    /// Runs only the parsing both parts start with, so it can be timed on its own. `None` for
+    /// days without a separate parsing step. Days implement it with [`parsed`].
+    fn parse(&self, _input: &str) -> Option<Result<()>> {
+        None
+    }
+
+    /// Days whose input is compiled into the solution return it here. The runner uses it when
+    /// the input directory has no file for the day.
+    fn embedded_input(&self) -> Option<&'static str> {
+        None
+    }
+
+    /// What the input looks like, checked by `aoc --check-input`. `None` for days that only
+    /// get the checks every input has to pass.
+    fn input_shape(&self) -> Option<Shape> {
+        None
+    }
+}
+
+/// [`Solution::parse`] for a day whose parser returned `result`, which is kept from being
+/// optimized away. Infallible parsers can be wrapped in [`eyre::Ok`].
+pub fn parsed<T, E: Into<eyre::Report>>(result: Result<T, E>) -> Option<Result<()>> {
+    Some(
+        result
+            .map(|parsed| {
+                std::hint::black_box(parsed);
+            })
+            .map_err(Into::into),
+    )
+}
+
+/// Entry in a year's registry of solved days.
+#[derive(Clone, Copy)]
+pub struct Puzzle {
+    pub year: u32,
+    pub day: u32,
+    pub solution: &'static dyn Solution,
+}
+
+impl Puzzle {
+    pub const fn new(year: u32, day: u32, solution: &'static dyn Solution) -> Self {
+        Self {
+            year,
+            day,
+            solution,
+        }
+    }
+
+    /// Solves a part inside a `part1` or `part2` span.
+    pub fn part(&self, part: u32, input: &str) -> Result<Answer> {
+        let (year, day) = (self.year, self.day);
+        match part {
+            1 => info_span!("part1", year, day).in_scope(|| self.solution.part1(input)),
+            2 => info_span!("part2", year, day).in_scope(|| self.solution.part2(input)),
+            _ => eyre::bail!("{} day{} has no part {}", self.year, self.day, part),
+        }
+    }
+
+    /// [`Solution::parse`] inside a `parse` span.
+    pub fn parse(&self, input: &str) -> Option<Result<()>> {
+        let (year, day) = (self.year, self.day);
+        info_span!("parse", year, day).in_scope(|| self.solution.parse(input))
+    }
+}
+
+impl std::fmt::Debug for Puzzle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} day{}", self.year, self.day)
+    }
+}